@@ -1,10 +1,19 @@
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, bail};
 use colored::*;
+use log::{debug, info, warn};
+use reqwest::StatusCode;
+use reqwest::header::{ETAG, IF_NONE_MATCH};
+use std::collections::HashSet;
+use std::env;
 use std::fs;
-use std::path::PathBuf;
-use std::time::{Duration, SystemTime};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Once, OnceLock};
+use std::time::{Duration, Instant, SystemTime};
 
-use crate::types::CratesData;
+use crate::links;
+use crate::types::{CratesData, SchemaMismatch};
 
 /// Get the cache directory path
 /// Returns the operating-system appropriate directory used for caching ratcrate data.
@@ -17,9 +26,153 @@ use crate::types::CratesData;
 /// Returns an `anyhow::Error` if the platform-specific directory cannot be determined.
 const REMOTE_URL: &str = "https://ratcrate.github.io/data/ratcrate.json";
 const CACHE_MAX_AGE_DAYS: u64 = 1;
+/// Environment variable that replaces `REMOTE_URL`, e.g. to use a mirror
+const URL_ENV: &str = "RATCRATE_URL";
+/// Environment variable that replaces the platform cache directory
+const CACHE_DIR_ENV: &str = "RATCRATE_CACHE_DIR";
+
+/// Guards the one-time warning about an unusable `RATCRATE_CACHE_DIR`
+static CACHE_DIR_WARNING: Once = Once::new();
+
+/// Guards the one-time warning about falling back to a temporary cache directory
+static TEMP_DIR_WARNING: Once = Once::new();
+
+/// Set once the cache lives in a temporary directory that won't survive a reboot
+static EPHEMERAL: AtomicBool = AtomicBool::new(false);
+
+/// Cache directory resolved and checked by the first successful `get_cache_dir`
+static CACHE_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// Dataset URL chosen at startup; unset means `REMOTE_URL`
+static REMOTE: OnceLock<String> = OnceLock::new();
+
+/// Set while the TUI owns the terminal, so progress notes don't scribble over it
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// Whether a dataset download is reading its body, for `download_progress`
+static DOWNLOADING: AtomicBool = AtomicBool::new(false);
+/// Bytes of the dataset body received so far
+static RECEIVED: AtomicU64 = AtomicU64::new(0);
+/// The download's `Content-Length`, or 0 when the server didn't send one
+static EXPECTED: AtomicU64 = AtomicU64::new(0);
+
+/// Active cache profile, set once at startup. `None` means the default profile.
+static PROFILE: OnceLock<Option<String>> = OnceLock::new();
+
+/// Select the cache profile for this process.
+///
+/// Profiles keep separate caches under `get_cache_dir()/<profile>/`. Names are limited to
+/// ASCII letters, digits, `-` and `_` so they are always a single path component.
+///
+/// # Errors
+/// Returns an error if the name is invalid or a profile was already selected.
+pub fn set_profile(name: Option<String>) -> Result<()> {
+    if let Some(name) = &name {
+        let valid = !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !valid {
+            anyhow::bail!(
+                "Invalid profile name '{}': use letters, digits, '-' or '_'",
+                name
+            );
+        }
+    }
+
+    PROFILE
+        .set(name)
+        .map_err(|_| anyhow::anyhow!("Cache profile already set"))
+}
+
+/// Select where the dataset is downloaded from: `url` (from `--url`), else `RATCRATE_URL`,
+/// else the built-in `REMOTE_URL`.
+///
+/// # Errors
+/// Returns an error if the chosen value is not an http(s) URL or a URL was already selected.
+pub fn set_remote_url(url: Option<String>) -> Result<()> {
+    let (url, source) = match url {
+        Some(url) => (url, "--url"),
+        None => match env::var(URL_ENV).ok().filter(|url| !url.trim().is_empty()) {
+            Some(url) => (url, URL_ENV),
+            None => return Ok(()),
+        },
+    };
+    let url = links::validate(&url).map_err(|e| anyhow::anyhow!("Invalid {}: {}", source, e))?;
+    info!("dataset URL from {}: {}", source, url);
+    REMOTE
+        .set(url.to_string())
+        .map_err(|_| anyhow::anyhow!("Dataset URL already set"))
+}
+
+/// Stop (or resume) printing progress notes such as "Loaded from cache" to stderr.
+pub fn set_quiet(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+}
+
+/// Print a progress note to stderr unless `set_quiet` silenced them.
+fn notice(message: impl std::fmt::Display) {
+    if !QUIET.load(Ordering::Relaxed) {
+        eprintln!("{}", message);
+    }
+}
+
+/// Bytes received by the running dataset download and its expected size, if the server sent
+/// a `Content-Length`. `None` when no download is running.
+pub fn download_progress() -> Option<(u64, Option<u64>)> {
+    if !DOWNLOADING.load(Ordering::Relaxed) {
+        return None;
+    }
+    let expected = EXPECTED.load(Ordering::Relaxed);
+    Some((
+        RECEIVED.load(Ordering::Relaxed),
+        (expected > 0).then_some(expected),
+    ))
+}
+
+/// Name of the active cache profile, if one was selected.
+pub fn active_profile() -> Option<&'static str> {
+    PROFILE.get().and_then(|p| p.as_deref())
+}
 
 /// Get the cache directory path
+///
+/// `RATCRATE_CACHE_DIR` overrides the platform directory when it points somewhere writable;
+/// otherwise a warning is printed once and the platform directory is used. When that isn't
+/// writable either (a read-only home), a directory under the system temp dir is used with a
+/// warning, and `is_persistent` turns false.
+///
+/// The directory is resolved and probed for writability once per process; later calls
+/// return the stored path without touching the filesystem.
 pub fn get_cache_dir() -> Result<PathBuf> {
+    if let Some(dir) = CACHE_DIR.get() {
+        return Ok(dir.clone());
+    }
+    let dir = resolve_cache_dir()?;
+    Ok(CACHE_DIR.get_or_init(|| dir).clone())
+}
+
+fn resolve_cache_dir() -> Result<PathBuf> {
+    if let Some(dir) = env::var_os(CACHE_DIR_ENV).filter(|dir| !dir.is_empty()) {
+        let dir = PathBuf::from(dir);
+        match ensure_writable(&dir) {
+            Ok(()) => return Ok(dir),
+            Err(e) => CACHE_DIR_WARNING.call_once(|| {
+                warn!("{} {} is not usable: {:#}", CACHE_DIR_ENV, dir.display(), e);
+                eprintln!(
+                    "{}",
+                    format!(
+                        "⚠ {} {} is not usable ({:#}), falling back to the default cache directory",
+                        CACHE_DIR_ENV,
+                        dir.display(),
+                        e
+                    )
+                    .yellow()
+                );
+            }),
+        }
+    }
+
     let cache_dir = if cfg!(target_os = "windows") {
         dirs::data_local_dir()
             .context("Failed to get local data directory")?
@@ -30,13 +183,163 @@ pub fn get_cache_dir() -> Result<PathBuf> {
             .join("ratcrate")
     };
 
-    fs::create_dir_all(&cache_dir)?;
-    Ok(cache_dir)
+    match ensure_writable(&cache_dir) {
+        Ok(()) => Ok(cache_dir),
+        Err(e) => {
+            let temp_dir = env::temp_dir().join("ratcrate");
+            ensure_writable(&temp_dir).with_context(|| {
+                format!(
+                    "Neither {} ({:#}) nor {} is writable",
+                    cache_dir.display(),
+                    e,
+                    temp_dir.display()
+                )
+            })?;
+            EPHEMERAL.store(true, Ordering::Relaxed);
+            TEMP_DIR_WARNING.call_once(|| {
+                warn!("{} is not usable: {:#}", cache_dir.display(), e);
+                eprintln!(
+                    "{}",
+                    format!(
+                        "⚠ {} is not usable ({:#}), using {} for this session",
+                        cache_dir.display(),
+                        e,
+                        temp_dir.display()
+                    )
+                    .yellow()
+                );
+            });
+            Ok(temp_dir)
+        }
+    }
+}
+
+/// Whether the cache, favorites and session live somewhere that survives a reboot.
+///
+/// False once `get_cache_dir` had to fall back to the system temp dir.
+pub fn is_persistent() -> bool {
+    !EPHEMERAL.load(Ordering::Relaxed)
+}
+
+/// Create `dir` if needed and check that files can be written to it.
+fn ensure_writable(dir: &Path) -> Result<()> {
+    fs::create_dir_all(dir).context("cannot create directory")?;
+    let probe = dir.join(".ratcrate-write-test");
+    fs::write(&probe, b"").context("directory is not writable")?;
+    fs::remove_file(&probe)?;
+    Ok(())
+}
+
+/// Get the directory holding the active profile's cache files.
+///
+/// The default profile uses the cache directory itself, so existing caches keep working.
+pub fn get_profile_dir() -> Result<PathBuf> {
+    let cache_dir = get_cache_dir()?;
+    match active_profile() {
+        Some(profile) => {
+            let dir = cache_dir.join(profile);
+            fs::create_dir_all(&dir)?;
+            Ok(dir)
+        }
+        None => Ok(cache_dir),
+    }
 }
 
 /// Get the cache file path
 pub fn get_cache_file() -> Result<PathBuf> {
-    Ok(get_cache_dir()?.join("ratcrate.json"))
+    Ok(get_profile_dir()?.join("ratcrate.json"))
+}
+
+/// URL the dataset is downloaded from
+pub fn remote_url() -> &'static str {
+    REMOTE.get().map_or(REMOTE_URL, String::as_str)
+}
+
+/// How long a cached dataset is considered fresh
+pub fn cache_max_age() -> Duration {
+    Duration::from_secs(CACHE_MAX_AGE_DAYS * 24 * 3600)
+}
+
+/// Where a loaded dataset came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataOrigin {
+    /// Read from the local cache file
+    Cache,
+    /// Downloaded during this run
+    Download,
+}
+
+/// Where a loaded dataset came from and when it was fetched
+#[derive(Debug, Clone, Copy)]
+pub struct Provenance {
+    pub origin: DataOrigin,
+    /// When the data was downloaded: the cache file's write time, or now for a download
+    pub fetched_at: SystemTime,
+}
+
+impl Provenance {
+    /// Data downloaded just now
+    pub fn downloaded() -> Self {
+        Self {
+            origin: DataOrigin::Download,
+            fetched_at: SystemTime::now(),
+        }
+    }
+
+    /// Data read from the cache file, dated by its write time
+    pub fn cached() -> Self {
+        let fetched_at = get_cache_file()
+            .and_then(|path| Ok(fs::metadata(path)?.modified()?))
+            .unwrap_or_else(|_| SystemTime::now());
+        Self {
+            origin: DataOrigin::Cache,
+            fetched_at,
+        }
+    }
+
+    /// Time since the data was downloaded
+    pub fn age(&self) -> Duration {
+        SystemTime::now()
+            .duration_since(self.fetched_at)
+            .unwrap_or_default()
+    }
+}
+
+/// Snapshot of the cache state, for diagnostics.
+#[derive(Debug, Clone)]
+pub struct CacheInfo {
+    pub path: PathBuf,
+    /// File size in bytes, `None` if the file does not exist
+    pub size: Option<u64>,
+    /// Time since the file was last written, `None` if the file does not exist
+    pub age: Option<Duration>,
+    pub stale: bool,
+    pub remote_url: &'static str,
+    pub max_age: Duration,
+}
+
+/// Collect the resolved cache path, size, age and staleness.
+pub fn cache_info() -> Result<CacheInfo> {
+    let path = get_cache_file()?;
+    let (size, age) = match fs::metadata(&path) {
+        Ok(metadata) => {
+            let age = metadata
+                .modified()
+                .ok()
+                .and_then(|m| SystemTime::now().duration_since(m).ok());
+            (Some(metadata.len()), age)
+        }
+        Err(_) => (None, None),
+    };
+
+    Ok(CacheInfo {
+        path,
+        size,
+        age,
+        stale: is_cache_stale()?,
+        remote_url: remote_url(),
+        max_age: cache_max_age(),
+    })
 }
 
 /// Check if cache is stale
@@ -44,6 +347,7 @@ pub fn is_cache_stale() -> Result<bool> {
     let cache_file = get_cache_file()?;
 
     if !cache_file.exists() {
+        debug!("cache miss: {} does not exist", cache_file.display());
         return Ok(true);
     }
 
@@ -51,49 +355,322 @@ pub fn is_cache_stale() -> Result<bool> {
     let modified = metadata.modified()?;
     let age = SystemTime::now().duration_since(modified)?;
 
-    Ok(age > Duration::from_secs(CACHE_MAX_AGE_DAYS * 24 * 3600))
+    let stale = age > cache_max_age();
+    debug!(
+        "cache age {}s, max {}s: {}",
+        age.as_secs(),
+        cache_max_age().as_secs(),
+        if stale { "stale" } else { "fresh" }
+    );
+    Ok(stale)
+}
+
+/// `ETag` of the cached download, kept next to the cache file
+pub fn get_etag_file() -> Result<PathBuf> {
+    Ok(get_profile_dir()?.join("ratcrate.etag"))
 }
 
 /// Load data from cache
 pub fn load_from_cache() -> Result<CratesData> {
+    let data = read_cache()?;
+    record_snapshot(&data);
+
+    notice("✓ Loaded from cache".green());
+    Ok(data)
+}
+
+/// Read and parse the cache file without printing anything.
+fn read_cache() -> Result<CratesData> {
     let cache_file = get_cache_file()?;
     let content = fs::read_to_string(&cache_file).context("Failed to read cache file")?;
 
-    let data: CratesData = serde_json::from_str(&content).context("Failed to parse cache file")?;
-
-    println!("{}", "✓ Loaded from cache".green());
+    let started = Instant::now();
+    let data: CratesData = serde_json::from_str(&content)
+        .context("Failed to parse cache file")
+        .inspect_err(|e| warn!("{:#} ({})", e, cache_file.display()))?;
+    let data = data.upgrade().inspect_err(|e| warn!("cached {}", e))?;
+    info!(
+        "cache hit: {} crates, dataset {}, {} bytes parsed in {:?}",
+        data.crates.len(),
+        data.metadata.version,
+        content.len(),
+        started.elapsed()
+    );
     Ok(data)
 }
 
-/// Download fresh data from GitHub
-pub fn download_fresh_data() -> Result<CratesData> {
-    println!("{}", "📡 Downloading latest data from GitHub...".cyan());
+/// The stored `ETag`, if the cache it describes still exists
+fn read_etag() -> Option<String> {
+    if !get_cache_file().ok()?.exists() {
+        return None;
+    }
+    let etag = fs::read_to_string(get_etag_file().ok()?).ok()?;
+    Some(etag.trim().to_string()).filter(|etag| !etag.is_empty())
+}
+
+/// GET the dataset, conditionally when an `ETag` is given.
+fn request_data(etag: Option<&str>) -> Result<reqwest::blocking::Response> {
+    let mut request = reqwest::blocking::Client::new().get(remote_url());
+    if let Some(etag) = etag {
+        request = request.header(IF_NONE_MATCH, etag);
+    }
+    request
+        .send()
+        .context("Failed to download data")
+        .inspect_err(|e| warn!("{:#}", e))
+}
 
-    let response = reqwest::blocking::get(REMOTE_URL).context("Failed to download data")?;
+/// Read a response body in chunks, publishing progress for `download_progress`.
+fn read_body(mut response: reqwest::blocking::Response) -> Result<Vec<u8>> {
+    let expected = response.content_length();
+    EXPECTED.store(expected.unwrap_or(0), Ordering::Relaxed);
+    RECEIVED.store(0, Ordering::Relaxed);
+    DOWNLOADING.store(true, Ordering::Relaxed);
+
+    // The header is only a hint; don't let it reserve an absurd amount up front
+    let mut body = Vec::with_capacity(expected.unwrap_or(0).min(64 << 20) as usize);
+    let mut chunk = [0; 64 * 1024];
+    let read = loop {
+        match response.read(&mut chunk) {
+            Ok(0) => break Ok(()),
+            Ok(n) => {
+                body.extend_from_slice(&chunk[..n]);
+                RECEIVED.fetch_add(n as u64, Ordering::Relaxed);
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(e) => break Err(e),
+        }
+    };
+    DOWNLOADING.store(false, Ordering::Relaxed);
+    read.context("Failed to download data")?;
+    Ok(body)
+}
+
+/// Download fresh data and write it to the cache without printing anything.
+///
+/// Safe to call from a background thread while the TUI owns the terminal.
+///
+/// When the cache has a stored `ETag`, the request is conditional: a `304 Not Modified` keeps
+/// the cached data (marking it fresh again) and counts an ETag hit; a full download counts a
+/// miss.
+pub fn fetch_remote_data() -> Result<CratesData> {
+    info!("downloading {}", remote_url());
+    let started = Instant::now();
+    let etag = read_etag();
+    let mut response = request_data(etag.as_deref())?;
+
+    if response.status() == StatusCode::NOT_MODIFIED {
+        match read_cache() {
+            Ok(mut data) => {
+                info!("not modified since ETag {:?}, keeping the cache", etag);
+                data.metadata.statistics.record_etag(true);
+                // Rewriting also resets the cache age
+                fs::write(get_cache_file()?, serde_json::to_string_pretty(&data)?)?;
+                record_snapshot(&data);
+                return Ok(data);
+            }
+            Err(e) => {
+                warn!(
+                    "not modified, but the cache is unusable ({:#}); downloading",
+                    e
+                );
+                response = request_data(None)?;
+            }
+        }
+    }
 
     if !response.status().is_success() {
+        warn!("server returned status {}", response.status());
         anyhow::bail!("Server returned status: {}", response.status());
     }
+    debug!(
+        "response {} after {:?}",
+        response.status(),
+        started.elapsed()
+    );
 
-    let data: CratesData = response.json().context("Failed to parse downloaded data")?;
+    let new_etag = response
+        .headers()
+        .get(ETAG)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+    let body = read_body(response).inspect_err(|e| warn!("{:#}", e))?;
+    debug!(
+        "received {} bytes after {:?}",
+        body.len(),
+        started.elapsed()
+    );
+    let parse_started = Instant::now();
+    let data: CratesData = serde_json::from_slice(&body)
+        .context("Failed to parse downloaded data")
+        .inspect_err(|e| warn!("{:#}", e))?;
+    let mut data = data
+        .upgrade()
+        .inspect_err(|e| warn!("downloaded {}", e))
+        .context("Downloaded data can't be used; a newer ratcrate-tui may be needed")?;
+    info!(
+        "downloaded {} crates, dataset {}, parsed in {:?} ({:?} total)",
+        data.crates.len(),
+        data.metadata.version,
+        parse_started.elapsed(),
+        started.elapsed()
+    );
+
+    data.metadata.statistics.record_etag(false);
 
     // Save to cache
     let cache_file = get_cache_file()?;
     let json = serde_json::to_string_pretty(&data)?;
     fs::write(&cache_file, json)?;
+    record_snapshot(&data);
+
+    // Best effort: without a stored ETag the next download is simply unconditional
+    let etag_file = get_etag_file()?;
+    let stored = match &new_etag {
+        Some(etag) => fs::write(&etag_file, etag),
+        None => fs::remove_file(&etag_file).or_else(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => Ok(()),
+            _ => Err(e),
+        }),
+    };
+    if let Err(e) = stored {
+        warn!("cannot update {}: {}", etag_file.display(), e);
+    }
+
+    Ok(data)
+}
+
+/// Directory holding the crate id lists of earlier dataset versions
+fn get_snapshot_dir() -> Result<PathBuf> {
+    let dir = get_profile_dir()?.join("snapshots");
+    fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+/// Snapshot file for a dataset version; characters unsafe in a file name become `_`.
+fn snapshot_file(version: &str) -> Result<PathBuf> {
+    let name: String = version
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    Ok(get_snapshot_dir()?.join(format!("{}.json", name)))
+}
+
+/// Remember which crate ids this dataset version contains, once per version.
+///
+/// Best effort: a snapshot that cannot be written only disables `:newsince` for that version.
+pub fn record_snapshot(data: &CratesData) {
+    let Ok(path) = snapshot_file(&data.metadata.version) else {
+        return;
+    };
+    if path.exists() {
+        return;
+    }
+    let ids: Vec<&str> = data.crates.iter().map(|c| c.id.as_str()).collect();
+    if let Ok(json) = serde_json::to_string(&ids) {
+        match fs::write(&path, json) {
+            Ok(()) => debug!("recorded snapshot of dataset {}", data.metadata.version),
+            Err(e) => warn!("cannot write snapshot {}: {}", path.display(), e),
+        }
+    }
+}
+
+/// Crate ids stored for a dataset version.
+///
+/// # Errors
+/// Returns an error if no snapshot of that version was recorded or it cannot be parsed.
+pub fn load_snapshot(version: &str) -> Result<HashSet<String>> {
+    let path = snapshot_file(version)?;
+    let content = fs::read_to_string(&path)
+        .with_context(|| format!("No snapshot of dataset version {} is cached", version))?;
+    serde_json::from_str(&content).context("Failed to parse snapshot file")
+}
+
+/// Delete every recorded snapshot. Returns how many were removed.
+pub fn clear_snapshots() -> Result<usize> {
+    let mut removed = 0;
+    for entry in fs::read_dir(get_snapshot_dir()?)? {
+        fs::remove_file(entry?.path())?;
+        removed += 1;
+    }
+    Ok(removed)
+}
 
-    println!(
-        "{}",
+/// Dataset versions with a recorded snapshot, sorted by name.
+pub fn snapshot_versions() -> Vec<String> {
+    let Ok(entries) = get_snapshot_dir().and_then(|dir| Ok(fs::read_dir(dir)?)) else {
+        return vec![];
+    };
+    let mut versions: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            entry
+                .file_name()
+                .to_str()?
+                .strip_suffix(".json")
+                .map(str::to_string)
+        })
+        .collect();
+    versions.sort();
+    versions
+}
+
+/// Download fresh data from GitHub
+pub fn download_fresh_data() -> Result<CratesData> {
+    notice("📡 Downloading latest data from GitHub...".cyan());
+
+    let data = fetch_remote_data()?;
+
+    notice(
         format!(
             "✓ Downloaded and cached {} crates",
             data.metadata.total_crates
         )
-        .green()
+        .green(),
     );
 
     Ok(data)
 }
 
+/// Load the cache even when it is stale, for a "show now, refresh in the background" startup.
+///
+/// Returns the data, where it came from, and whether it is stale and should be refreshed.
+/// Downloads (blocking) only when there is no readable cache.
+///
+/// # Errors
+/// Returns an error if there is no usable cache and the download fails.
+pub fn get_data_offline_first() -> Result<(CratesData, Provenance, bool)> {
+    if get_cache_file()?.exists() {
+        match load_from_cache() {
+            Ok(data) => return Ok((data, Provenance::cached(), is_cache_stale()?)),
+            Err(e) => notice(format!("⚠ {:#}, downloading fresh data...", e).yellow()),
+        }
+    }
+    Ok((download_fresh_data()?, Provenance::downloaded(), false))
+}
+
+/// Load the cache without touching the network, however stale it is (`--offline`).
+///
+/// # Errors
+/// Returns an error if there is no cache file yet or it cannot be read.
+pub fn get_data_offline() -> Result<(CratesData, Provenance)> {
+    let cache_file = get_cache_file()?;
+    if !cache_file.exists() {
+        bail!(
+            "Offline and no cached data at {}; run once with a network connection first",
+            cache_file.display()
+        );
+    }
+    info!("offline, using the cache regardless of age");
+    Ok((load_from_cache()?, Provenance::cached()))
+}
+
 /// Get the Crates data for the TUI.
 ///
 /// This function returns `CratesData` either by loading from a local cache (if present and fresh)
@@ -103,17 +680,29 @@ pub fn download_fresh_data() -> Result<CratesData> {
 /// # Arguments
 /// * `force_refresh` - bool: if true, ignore cache and download fresh data.
 ///
+/// Returns the data along with where it came from.
+///
 /// # Errors
 /// Returns an error if network download or cache IO operations fail.
-pub fn get_data(force_refresh: bool) -> Result<CratesData> {
+pub fn get_data(force_refresh: bool) -> Result<(CratesData, Provenance)> {
     if force_refresh {
-        println!("{}", "🔄 Force refresh requested".yellow());
-        download_fresh_data()
+        info!("force refresh requested");
+        notice("🔄 Force refresh requested".yellow());
+        Ok((download_fresh_data()?, Provenance::downloaded()))
     } else if is_cache_stale()? {
-        println!("{}", "⚠ Cache is stale, downloading fresh data...".yellow());
-        download_fresh_data()
+        info!("cache stale or missing, downloading");
+        notice("⚠ Cache is stale, downloading fresh data...".yellow());
+        Ok((download_fresh_data()?, Provenance::downloaded()))
     } else {
-        load_from_cache()
+        match load_from_cache() {
+            Ok(data) => Ok((data, Provenance::cached())),
+            // A cache from an older (or newer) build is replaced rather than fatal
+            Err(e) if e.is::<SchemaMismatch>() => {
+                notice(format!("⚠ Cached {}, downloading fresh data...", e).yellow());
+                Ok((download_fresh_data()?, Provenance::downloaded()))
+            }
+            Err(e) => Err(e),
+        }
     }
 }
 
@@ -127,8 +716,11 @@ mod tests {
     #[test]
     fn test_get_cache_dir_returns_path() {
         let p = get_cache_dir().expect("get_cache_dir should succeed on supported platforms");
+        // Resolved once, then served from the stored path
+        assert_eq!(CACHE_DIR.get(), Some(&p));
+        assert_eq!(get_cache_dir().unwrap(), p);
         // Path should be non-empty and absolute
-        assert!(p.as_os_str().len() > 0);
+        assert!(!p.as_os_str().is_empty());
         assert!(p.is_absolute() || p.starts_with("/"));
     }
 }
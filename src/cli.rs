@@ -0,0 +1,184 @@
+//! cli.rs — Command-line argument parsing for the ratcrate TUI
+//!
+//! The argument surface is small, so it is parsed by hand rather than pulling in a parser crate.
+//
+use anyhow::{Result, bail};
+
+pub const USAGE: &str = "\
+Usage: ratcrate-tui [OPTIONS] [QUERY]...
+       ratcrate-tui search [--json] [--downloads] <QUERY>...
+
+Arguments:
+  [QUERY]...        Open with the list filtered to crates matching this search
+
+Commands:
+  search <QUERY>... Print the names of matching crates and exit (status 1 if none match)
+
+Options:
+  --profile <name>  Use a separate cache for this profile (default: shared cache)
+  --url <url>       Download the dataset from this URL, e.g. a mirror (overrides RATCRATE_URL)
+  --theme <name>    Use a built-in theme for this session: default, ocean, forest or mono
+                    (overrides the config file)
+  --offline         Never download; use the cached data however old it is
+  --dump-config     Print the resolved configuration and exit
+  --json            With `search`: print matching crates as a JSON array
+  --downloads       With `search`: print total downloads next to each name
+  -h, --help        Print this help and exit";
+
+/// Options parsed from the command line
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Args {
+    /// Cache profile name; `None` keeps the default cache location
+    pub profile: Option<String>,
+    /// Dataset URL; `None` uses `RATCRATE_URL` or the built-in one
+    pub url: Option<String>,
+    /// Built-in theme name; `None` uses the config file's theme
+    pub theme: Option<String>,
+    /// Search to run on startup; `None` opens the full list
+    pub search: Option<String>,
+    /// Print the crates matching `search` and exit instead of opening the TUI
+    pub print_search: bool,
+    /// Print search results as JSON
+    pub json: bool,
+    /// Print downloads next to search results
+    pub downloads: bool,
+    /// Never download; use the cache even when stale
+    pub offline: bool,
+    /// Print the resolved configuration and exit
+    pub dump_config: bool,
+    /// Print usage and exit
+    pub help: bool,
+}
+
+impl Args {
+    /// Parse the process arguments, skipping the binary name.
+    pub fn parse() -> Result<Self> {
+        Self::parse_from(std::env::args().skip(1))
+    }
+
+    /// Parse arguments from any iterator (without the binary name).
+    pub fn parse_from<I, S>(args: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut parsed = Args::default();
+        let mut args = args.into_iter().map(Into::into);
+        let mut query: Vec<String> = vec![];
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-h" | "--help" => parsed.help = true,
+                "--dump-config" => parsed.dump_config = true,
+                "--offline" => parsed.offline = true,
+                "--json" => parsed.json = true,
+                "--downloads" => parsed.downloads = true,
+                // `search` as the first word selects the subcommand rather than a query
+                "search" if query.is_empty() && !parsed.print_search => parsed.print_search = true,
+                "--profile" => {
+                    let Some(name) = args.next() else {
+                        bail!("--profile requires a name\n\n{}", USAGE);
+                    };
+                    parsed.profile = Some(name);
+                }
+                "--theme" => {
+                    let Some(name) = args.next() else {
+                        bail!("--theme requires a name\n\n{}", USAGE);
+                    };
+                    parsed.theme = Some(name);
+                }
+                "--url" => {
+                    let Some(url) = args.next() else {
+                        bail!("--url requires a URL\n\n{}", USAGE);
+                    };
+                    parsed.url = Some(url);
+                }
+                other => {
+                    if let Some(name) = other.strip_prefix("--profile=") {
+                        parsed.profile = Some(name.to_string());
+                    } else if let Some(name) = other.strip_prefix("--theme=") {
+                        parsed.theme = Some(name.to_string());
+                    } else if let Some(url) = other.strip_prefix("--url=") {
+                        parsed.url = Some(url.to_string());
+                    } else if !other.starts_with('-') {
+                        query.push(other.to_string());
+                    } else {
+                        bail!("Unknown argument '{}'\n\n{}", other, USAGE);
+                    }
+                }
+            }
+        }
+
+        if !query.is_empty() {
+            parsed.search = Some(query.join(" "));
+        }
+        if parsed.print_search && parsed.search.is_none() {
+            bail!("search requires a query\n\n{}", USAGE);
+        }
+        if (parsed.json || parsed.downloads) && !parsed.print_search {
+            bail!(
+                "--json and --downloads only apply to the search command\n\n{}",
+                USAGE
+            );
+        }
+        Ok(parsed)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Unit tests for cli.rs
+// ---------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_profile_forms() {
+        let a = Args::parse_from(["--profile", "staging"]).unwrap();
+        let b = Args::parse_from(["--profile=staging"]).unwrap();
+        assert_eq!(a.profile.as_deref(), Some("staging"));
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_parse_theme_forms() {
+        let a = Args::parse_from(["--theme", "ocean", "--profile", "demo"]).unwrap();
+        let b = Args::parse_from(["--profile=demo", "--theme=ocean"]).unwrap();
+        assert_eq!(a.theme.as_deref(), Some("ocean"));
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_parse_positional_query() {
+        let a = Args::parse_from(["--profile", "demo", "tokio", "tui"]).unwrap();
+        assert_eq!(a.search.as_deref(), Some("tokio tui"));
+        assert_eq!(a.profile.as_deref(), Some("demo"));
+        assert_eq!(Args::parse_from(Vec::<String>::new()).unwrap().search, None);
+    }
+
+    #[test]
+    fn test_parse_search_command() {
+        let a = Args::parse_from(["search", "--json", "tokio", "tui"]).unwrap();
+        assert!(a.print_search && a.json);
+        assert_eq!(a.search.as_deref(), Some("tokio tui"));
+
+        // Only the first word selects the command
+        let b = Args::parse_from(["tui", "search"]).unwrap();
+        assert!(!b.print_search);
+        assert_eq!(b.search.as_deref(), Some("tui search"));
+
+        assert!(Args::parse_from(["search"]).is_err());
+        assert!(Args::parse_from(["--json", "tokio"]).is_err());
+
+        let offline = Args::parse_from(["search", "tokio", "--offline"]).unwrap();
+        assert!(offline.print_search && offline.offline);
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_and_missing_values() {
+        assert!(Args::parse_from(["--bogus"]).is_err());
+        assert!(Args::parse_from(["--profile"]).is_err());
+        assert!(Args::parse_from(["--theme"]).is_err());
+        assert!(Args::parse_from(["--url"]).is_err());
+    }
+}
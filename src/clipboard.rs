@@ -0,0 +1,25 @@
+//! clipboard.rs — Copying text to the system clipboard
+//!
+//! The clipboard is opened on first use and kept open for the rest of the run: on X11 the
+//! copied text is only served while the handle is alive.
+//
+use anyhow::{Context, Result};
+
+#[derive(Default)]
+pub struct Clipboard {
+    inner: Option<arboard::Clipboard>,
+}
+
+impl Clipboard {
+    /// Replace the clipboard contents with `text`.
+    ///
+    /// # Errors
+    /// Returns an error when no clipboard is reachable (e.g. headless or over SSH).
+    pub fn copy(&mut self, text: String) -> Result<()> {
+        let clipboard = match &mut self.inner {
+            Some(clipboard) => clipboard,
+            slot @ None => slot.insert(arboard::Clipboard::new().context("Clipboard unavailable")?),
+        };
+        clipboard.set_text(text).context("Clipboard unavailable")
+    }
+}
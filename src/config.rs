@@ -0,0 +1,118 @@
+//! config.rs — User configuration for the ratcrate TUI
+//!
+//! Settings are read from `config.toml` in the platform config directory
+//! (e.g. `~/.config/ratcrate/config.toml` on Linux). Every field is optional; anything left
+//! out falls back to the built-in default.
+//
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+/// Top-level configuration file contents
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub theme: ThemeConfig,
+    pub ui: UiConfig,
+    pub cache: CacheConfig,
+}
+
+/// `[cache]` section of the config file
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CacheConfig {
+    /// Start from a stale cache right away and refresh it in the background
+    pub offline_first: bool,
+}
+
+/// `[ui]` section of the config file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UiConfig {
+    /// Start with one line per crate in the list instead of two
+    pub compact_list: bool,
+    /// Show the marker gutter (favorites and visited crates) left of the list
+    pub gutter: bool,
+    /// Render core libraries like community crates (no star, no yellow)
+    pub mute_core: bool,
+    /// Mark each crate in the list as growing or stable by its recent share of downloads
+    pub trend_markers: bool,
+    /// After a filter or search, select the most downloaded match instead of the first row
+    pub select_top_result: bool,
+    /// Initial list order, e.g. "downloads-desc" or "name"; unset keeps the dataset order
+    pub default_sort: Option<String>,
+    /// Rows kept visible above and below the selection when scrolling (like vim's `scrolloff`)
+    pub scroll_margin: usize,
+    /// Description lines shown in the detail pane before it is cut short; 0 always shows all
+    pub description_lines: usize,
+    /// How long to wait for input between redraws while idle, in milliseconds
+    pub poll_interval_ms: u64,
+}
+
+impl Default for UiConfig {
+    fn default() -> Self {
+        Self {
+            compact_list: false,
+            gutter: true,
+            mute_core: false,
+            trend_markers: true,
+            select_top_result: false,
+            default_sort: None,
+            scroll_margin: 2,
+            description_lines: 3,
+            poll_interval_ms: 250,
+        }
+    }
+}
+
+/// `[theme]` section of the config file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ThemeConfig {
+    /// Border style: "plain", "rounded", "double", "thick" or "none"
+    pub border: String,
+    /// Border color: a name ("cyan"), an index ("38") or hex ("#00afd7")
+    pub border_color: String,
+    /// Body text color; "reset" keeps the terminal's default foreground
+    pub text_color: String,
+    /// Selection background: "auto" picks one for light or dark terminals, or any color
+    pub highlight_bg: String,
+    /// Marker drawn before the selected row; unset uses the icon set's marker
+    pub highlight_symbol: Option<String>,
+}
+
+impl Default for ThemeConfig {
+    fn default() -> Self {
+        Self {
+            border: "plain".to_string(),
+            border_color: "cyan".to_string(),
+            text_color: "reset".to_string(),
+            highlight_bg: "auto".to_string(),
+            highlight_symbol: None,
+        }
+    }
+}
+
+/// Path of the config file, if the platform has a config directory.
+pub fn get_config_file() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("ratcrate").join("config.toml"))
+}
+
+/// Load the config file, or defaults if it does not exist.
+///
+/// # Errors
+/// Returns an error if the file exists but cannot be read or parsed.
+pub fn load() -> Result<Config> {
+    let Some(path) = get_config_file() else {
+        return Ok(Config::default());
+    };
+    if !path.exists() {
+        return Ok(Config::default());
+    }
+
+    let content = fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read config file {}", path.display()))?;
+    toml::from_str(&content)
+        .with_context(|| format!("Failed to parse config file {}", path.display()))
+}
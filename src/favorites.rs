@@ -0,0 +1,95 @@
+//! favorites.rs — Crates the user has starred
+//!
+//! Favorites are stored per profile next to the data cache, keyed by the crate's stable `id`
+//! with the name kept as a readable hint. The file is rewritten whenever a favorite changes.
+//! Favorites whose id is no longer in the dataset are kept, so they come back if it returns;
+//! one whose crate reappears under a new id is matched again by name.
+//
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::cache::get_profile_dir;
+use crate::types::CratePackage;
+
+/// One starred crate
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Favorite {
+    pub id: String,
+    /// Last known name; only for humans reading the file and for listing orphans
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Favorites {
+    pub entries: Vec<Favorite>,
+}
+
+/// Get the favorites file path
+pub fn get_favorites_file() -> Result<PathBuf> {
+    Ok(get_profile_dir()?.join("favorites.json"))
+}
+
+impl Favorites {
+    /// Load saved favorites, falling back to none.
+    pub fn load() -> Self {
+        get_favorites_file()
+            .ok()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// Write the favorites file.
+    pub fn save(&self) -> Result<()> {
+        let path = get_favorites_file()?;
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(&path, json)
+            .with_context(|| format!("Failed to write favorites file {}", path.display()))
+    }
+
+    pub fn contains(&self, id: &str) -> bool {
+        self.entries.iter().any(|f| f.id == id)
+    }
+
+    /// Star or unstar a crate. Returns whether it is now a favorite.
+    pub fn toggle(&mut self, c: &CratePackage) -> bool {
+        if self.contains(&c.id) {
+            self.entries.retain(|f| f.id != c.id);
+            false
+        } else {
+            self.entries.push(Favorite {
+                id: c.id.clone(),
+                name: c.name.clone(),
+            });
+            true
+        }
+    }
+
+    /// Refresh the name hints from the dataset, so renamed crates show their current name.
+    ///
+    /// A favorite whose id is gone but whose name matches a crate that isn't already starred
+    /// takes that crate's id, so a dataset that renumbered its crates keeps the stars.
+    pub fn resolve(&mut self, crates: &[CratePackage]) {
+        let mut ids = self.ids();
+        for favorite in &mut self.entries {
+            if let Some(c) = crates.iter().find(|c| c.id == favorite.id) {
+                favorite.name.clone_from(&c.name);
+            } else if let Some(c) = crates
+                .iter()
+                .find(|c| c.name == favorite.name && !ids.contains(&c.id))
+            {
+                ids.insert(c.id.clone());
+                favorite.id.clone_from(&c.id);
+            }
+        }
+    }
+
+    /// Ids of every favorite
+    pub fn ids(&self) -> HashSet<String> {
+        self.entries.iter().map(|f| f.id.clone()).collect()
+    }
+}
@@ -0,0 +1,529 @@
+//! filter.rs — Steps that shape the crate list
+//!
+//! The visible list is defined by a chain of `ListOp`s replayed over the full dataset, which
+//! lets the app step back through earlier lists without storing copies of them.
+//
+use chrono::{DateTime, NaiveDate, Utc};
+use semver::{Version, VersionReq};
+use std::cmp::Reverse;
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::sort::SortOrder;
+use crate::types::CratePackage;
+
+/// One step that shapes the crate list
+#[derive(Debug, Clone, PartialEq)]
+pub enum ListOp {
+    /// Every crate
+    All,
+    /// Core libraries only
+    Core,
+    /// Crates whose id is in this snapshot of the favorites
+    Favorites(HashSet<String>),
+    /// Crates tagged with this (normalized) category, or with none at all for `None`
+    Category(Option<String>),
+    /// Crates created within this age bucket; unparseable dates are excluded
+    Age(AgeBucket),
+    /// Crates with no stored documentation link
+    NoDocs,
+    /// Crates with no stored homepage link
+    NoHome,
+    /// Top N by total downloads
+    Top(usize),
+    /// Top N by weekly downloads
+    Recent(usize),
+    /// N newest by creation date
+    New(usize),
+    /// Crates whose id is not in the snapshot of an earlier dataset version
+    NewSince {
+        version: String,
+        known: HashSet<String>,
+    },
+    /// Name or description contains the (lowercase) query
+    Search(String),
+    /// Name fuzzily matches the (lowercase) query, or the description contains it; best
+    /// matches first
+    Fuzzy(String),
+    /// Keep crates with at least this many downloads
+    MinDownloads(u64),
+    /// Keep crates with at most this many downloads
+    MaxDownloads(u64),
+    /// Keep crates whose ratatui requirement starts within this range; crates without a
+    /// readable requirement are dropped
+    Compat(VersionReq),
+    /// Keep only these names (lowercase), in this order
+    Allow(Vec<String>),
+    /// Drop these names (lowercase)
+    Deny(HashSet<String>),
+    /// Everything the current list hides
+    Invert,
+    /// Reorder the current list
+    Sort(SortOrder),
+    /// Flip the current list's order
+    Reverse,
+}
+
+impl ListOp {
+    /// Whether this step starts over from the full dataset instead of refining the current list.
+    pub fn resets(&self) -> bool {
+        matches!(
+            self,
+            ListOp::All
+                | ListOp::Core
+                | ListOp::Favorites(_)
+                | ListOp::Category(_)
+                | ListOp::Age(_)
+                | ListOp::NoDocs
+                | ListOp::NoHome
+                | ListOp::Top(_)
+                | ListOp::Recent(_)
+                | ListOp::New(_)
+                | ListOp::NewSince { .. }
+                | ListOp::Search(_)
+                | ListOp::Fuzzy(_)
+        )
+    }
+
+    /// Apply this step, given the full dataset and the list produced by the previous steps.
+    pub fn apply(&self, all: &[CratePackage], current: Vec<CratePackage>) -> Vec<CratePackage> {
+        match self {
+            ListOp::All => all.to_vec(),
+            ListOp::Core => all.iter().filter(|c| c.is_core_library).cloned().collect(),
+            ListOp::Favorites(ids) => all
+                .iter()
+                .filter(|c| ids.contains(&c.id))
+                .cloned()
+                .collect(),
+            ListOp::Category(category) => all
+                .iter()
+                .filter(|c| has_category(c, category.as_deref()))
+                .cloned()
+                .collect(),
+            ListOp::Age(bucket) => {
+                let now = Utc::now();
+                all.iter()
+                    .filter(|c| AgeBucket::of(&c.created_at, now) == Some(*bucket))
+                    .cloned()
+                    .collect()
+            }
+            ListOp::NoDocs => all
+                .iter()
+                .filter(|c| is_missing(&c.documentation))
+                .cloned()
+                .collect(),
+            ListOp::NoHome => all
+                .iter()
+                .filter(|c| is_missing(&c.homepage))
+                .cloned()
+                .collect(),
+            ListOp::Top(limit) => {
+                let mut sorted = all.to_vec();
+                sorted.sort_by_key(|c| Reverse(c.downloads));
+                sorted.truncate(*limit);
+                sorted
+            }
+            ListOp::Recent(limit) => {
+                let mut sorted = all.to_vec();
+                sorted.sort_by_key(|c| Reverse(c.recent_downloads));
+                sorted.truncate(*limit);
+                sorted
+            }
+            ListOp::New(limit) => {
+                let mut sorted = all.to_vec();
+                sorted.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+                sorted.truncate(*limit);
+                sorted
+            }
+            ListOp::NewSince { known, .. } => all
+                .iter()
+                .filter(|c| !known.contains(&c.id))
+                .cloned()
+                .collect(),
+            ListOp::Search(query) => all
+                .iter()
+                .filter(|c| matches_search(c, query))
+                .cloned()
+                .collect(),
+            ListOp::Fuzzy(query) => {
+                let mut scored: Vec<(i64, &CratePackage)> = all
+                    .iter()
+                    .filter_map(|c| fuzzy_score(c, query).map(|score| (score, c)))
+                    .collect();
+                // Stable, so equal scores keep dataset order
+                scored.sort_by_key(|(score, _)| Reverse(*score));
+                scored.into_iter().map(|(_, c)| c.clone()).collect()
+            }
+            ListOp::MinDownloads(n) => current.into_iter().filter(|c| c.downloads >= *n).collect(),
+            ListOp::MaxDownloads(n) => current.into_iter().filter(|c| c.downloads <= *n).collect(),
+            ListOp::Compat(req) => current
+                .into_iter()
+                .filter(|c| ratatui_version(c).is_some_and(|v| req.matches(&v)))
+                .collect(),
+            ListOp::Allow(names) => names
+                .iter()
+                .flat_map(|name| {
+                    current
+                        .iter()
+                        .filter(move |c| c.name.to_lowercase() == *name)
+                })
+                .cloned()
+                .collect(),
+            ListOp::Deny(names) => current
+                .into_iter()
+                .filter(|c| !names.contains(&c.name.to_lowercase()))
+                .collect(),
+            ListOp::Invert => {
+                let shown: HashSet<&str> = current.iter().map(|c| c.id.as_str()).collect();
+                all.iter()
+                    .filter(|c| !shown.contains(c.id.as_str()))
+                    .cloned()
+                    .collect()
+            }
+            ListOp::Sort(order) => {
+                let mut current = current;
+                order.sort(&mut current);
+                current
+            }
+            ListOp::Reverse => current.into_iter().rev().collect(),
+        }
+    }
+
+    /// Short description, e.g. "top 10" or "search 'tui'"
+    pub fn label(&self) -> String {
+        match self {
+            ListOp::All => "all".to_string(),
+            ListOp::Core => "core".to_string(),
+            ListOp::Favorites(_) => "favorites".to_string(),
+            ListOp::Category(Some(category)) => format!("category '{}'", category),
+            ListOp::Category(None) => "uncategorized".to_string(),
+            ListOp::Age(bucket) => format!("age {}", bucket.name()),
+            ListOp::NoDocs => "nodocs".to_string(),
+            ListOp::NoHome => "nohome".to_string(),
+            ListOp::Top(n) => format!("top {}", n),
+            ListOp::Recent(n) => format!("recent {}", n),
+            ListOp::New(n) => format!("new {}", n),
+            ListOp::NewSince { version, .. } => format!("new since {}", version),
+            ListOp::Search(q) => format!("search! '{}'", q),
+            ListOp::Fuzzy(q) => format!("search '{}'", q),
+            ListOp::MinDownloads(n) => format!("mindl {}", n),
+            ListOp::MaxDownloads(n) => format!("maxdl {}", n),
+            ListOp::Compat(req) => format!("compat {}", req),
+            ListOp::Allow(names) => format!("allowlist ({})", names.len()),
+            ListOp::Deny(names) => format!("denylist ({})", names.len()),
+            ListOp::Invert => "invert".to_string(),
+            ListOp::Sort(order) => format!("sort {}", order.label()),
+            ListOp::Reverse => "reverse".to_string(),
+        }
+    }
+}
+
+/// Case-insensitive substring match on name or description. `query` must be lowercase.
+pub fn matches_search(c: &CratePackage, query: &str) -> bool {
+    c.name.to_lowercase().contains(query) || c.description.to_lowercase().contains(query)
+}
+
+/// How well a crate matches a fuzzy query (lowercase); higher is better, `None` is no match.
+///
+/// An exact name beats a name containing the query, which beats the query's characters
+/// appearing in order in the name (so "ratui" finds "ratatui"). Runs of consecutive
+/// characters and matches at the start of a word score more, gaps and long names score
+/// less. A crate whose name doesn't match but whose description contains the query still
+/// matches, last.
+pub fn fuzzy_score(c: &CratePackage, query: &str) -> Option<i64> {
+    let name = c.name.to_lowercase();
+    if name == query {
+        return Some(10_000);
+    }
+    if let Some(at) = name.find(query) {
+        let start_bonus = if at == 0 { 1_000 } else { 0 };
+        return Some(5_000 + start_bonus - name.len() as i64);
+    }
+    if let Some(score) = subsequence_score(&name, query) {
+        return Some(score.max(1));
+    }
+    c.description.to_lowercase().contains(query).then_some(0)
+}
+
+/// Score `query` as an in-order subsequence of `name`, or `None` if it isn't one
+fn subsequence_score(name: &str, query: &str) -> Option<i64> {
+    let name: Vec<char> = name.chars().collect();
+    let mut score = 0;
+    let mut last: Option<usize> = None;
+    let mut from = 0;
+    for q in query.chars() {
+        let i = from + name[from..].iter().position(|&n| n == q)?;
+        score += 10;
+        if i == 0 || matches!(name[i - 1], '-' | '_') {
+            score += 10;
+        }
+        match last {
+            Some(l) if l + 1 == i => score += 15,
+            Some(l) => score -= (i - l - 1) as i64,
+            None => {}
+        }
+        last = Some(i);
+        from = i + 1;
+    }
+    // Among equal matches, prefer the shorter name
+    Some(score - name.len() as i64)
+}
+
+/// The lowest ratatui version a crate's requirement accepts: "0.26" and "^0.26" give 0.26.0,
+/// "=0.29.1" gives 0.29.1. `None` for an empty, wildcard or unparseable requirement.
+pub fn ratatui_version(c: &CratePackage) -> Option<Version> {
+    let raw = c.ratatui_dependency.version.trim();
+    if let Ok(version) = Version::parse(raw) {
+        return Some(version);
+    }
+    let req = VersionReq::parse(raw).ok()?;
+    let lowest = req.comparators.first()?;
+    let mut version = Version::new(
+        lowest.major,
+        lowest.minor.unwrap_or(0),
+        lowest.patch.unwrap_or(0),
+    );
+    version.pre = lowest.pre.clone();
+    Some(version)
+}
+
+/// Canonical form of a category for matching and counting: trimmed and lowercase.
+pub fn normalize_category(category: &str) -> String {
+    category.trim().to_lowercase()
+}
+
+/// A crate's distinct normalized categories, in dataset order, skipping blank ones.
+pub fn categories_of(c: &CratePackage) -> Vec<String> {
+    let mut categories: Vec<String> = vec![];
+    for category in c.categories.iter().flatten() {
+        let category = normalize_category(category);
+        if !category.is_empty() && !categories.contains(&category) {
+            categories.push(category);
+        }
+    }
+    categories
+}
+
+/// Whether a crate is tagged with the normalized `category`; `None` matches crates with no
+/// categories.
+pub fn has_category(c: &CratePackage, category: Option<&str>) -> bool {
+    let categories = categories_of(c);
+    match category {
+        Some(category) => categories.iter().any(|cat| cat == category),
+        None => categories.is_empty(),
+    }
+}
+
+/// How long ago a crate was first published
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AgeBucket {
+    /// Less than 3 months
+    New,
+    /// 3 months to 1 year
+    Recent,
+    /// 1 to 3 years
+    Mature,
+    /// More than 3 years
+    Old,
+}
+
+impl AgeBucket {
+    pub const ALL: [AgeBucket; 4] = [
+        AgeBucket::New,
+        AgeBucket::Recent,
+        AgeBucket::Mature,
+        AgeBucket::Old,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            AgeBucket::New => "new",
+            AgeBucket::Recent => "recent",
+            AgeBucket::Mature => "mature",
+            AgeBucket::Old => "old",
+        }
+    }
+
+    /// Human-readable range, e.g. "< 3 months"
+    pub fn range(self) -> &'static str {
+        match self {
+            AgeBucket::New => "< 3 months",
+            AgeBucket::Recent => "3 months - 1 year",
+            AgeBucket::Mature => "1 - 3 years",
+            AgeBucket::Old => "> 3 years",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|b| b.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Bucket for a `created_at` timestamp, or `None` if it cannot be parsed.
+    pub fn of(created_at: &str, now: DateTime<Utc>) -> Option<Self> {
+        let days = (now - parse_date(created_at)?).num_days();
+        Some(match days {
+            ..90 => AgeBucket::New,
+            90..365 => AgeBucket::Recent,
+            365..1095 => AgeBucket::Mature,
+            _ => AgeBucket::Old,
+        })
+    }
+}
+
+/// Parse an RFC 3339 timestamp or a plain `YYYY-MM-DD` date.
+pub fn parse_date(s: &str) -> Option<DateTime<Utc>> {
+    let s = s.trim();
+    DateTime::parse_from_rfc3339(s)
+        .map(|dt| dt.with_timezone(&Utc))
+        .ok()
+        .or_else(|| {
+            NaiveDate::parse_from_str(s, "%Y-%m-%d")
+                .ok()
+                .and_then(|d| d.and_hms_opt(0, 0, 0))
+                .map(|dt| dt.and_utc())
+        })
+}
+
+/// Read a list of crate names, one per line. Blank lines and `#` comments are skipped and
+/// duplicates dropped; names are lowercased.
+pub fn read_name_list(path: &Path) -> io::Result<Vec<String>> {
+    let mut seen = HashSet::new();
+    Ok(fs::read_to_string(path)?
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or("").trim().to_lowercase())
+        .filter(|name| !name.is_empty() && seen.insert(name.clone()))
+        .collect())
+}
+
+/// A link field counts as missing when unset or blank.
+fn is_missing(link: &Option<String>) -> bool {
+    link.as_deref().is_none_or(|s| s.trim().is_empty())
+}
+
+/// Rebuild a list by replaying `ops` over the full dataset.
+pub fn replay(ops: &[ListOp], all: &[CratePackage]) -> Vec<CratePackage> {
+    ops.iter()
+        .fold(all.to_vec(), |current, op| op.apply(all, current))
+}
+
+// ---------------------------------------------------------------------------
+// Unit tests for filter.rs
+// ---------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn package(id: &str, name: &str, downloads: u64) -> CratePackage {
+        CratePackage {
+            id: id.to_string(),
+            name: name.to_string(),
+            downloads,
+            ..Default::default()
+        }
+    }
+
+    fn names(crates: &[CratePackage]) -> Vec<&str> {
+        crates.iter().map(|c| c.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_fuzzy_search_ranks_by_score() {
+        let all = vec![
+            package("1", "tui-input", 10),
+            package("2", "ratatui-widgets", 10),
+            package("3", "ratatui", 10),
+            package("4", "gitui", 10),
+        ];
+
+        let fuzzy = |q: &str| names(&ListOp::Fuzzy(q.into()).apply(&all, vec![])).join(" ");
+        assert_eq!(fuzzy("rattui"), "ratatui ratatui-widgets");
+        assert_eq!(fuzzy("ratatui"), "ratatui ratatui-widgets");
+        assert_eq!(fuzzy("tui"), "tui-input gitui ratatui ratatui-widgets");
+        assert_eq!(fuzzy("zzz"), "");
+
+        // The exact variant keeps substring semantics
+        let exact = ListOp::Search("rattui".into()).apply(&all, vec![]);
+        assert!(exact.is_empty());
+    }
+
+    #[test]
+    fn test_compat_keeps_matching_ratatui_requirements() {
+        let requiring = |id, version: &str| CratePackage {
+            ratatui_dependency: crate::types::RatatuiDependency {
+                version: version.into(),
+                ..Default::default()
+            },
+            ..package(id, &format!("needs-{}", version), 0)
+        };
+        let all = vec![
+            requiring("1", "0.26"),
+            requiring("2", "^0.26.2"),
+            requiring("3", "0.29.0"),
+            requiring("4", ""),
+            requiring("5", "*"),
+            requiring("6", "latest"),
+        ];
+
+        let req = VersionReq::parse("^0.26").unwrap();
+        let kept = ListOp::Compat(req).apply(&all, all.clone());
+        assert_eq!(names(&kept), vec!["needs-0.26", "needs-^0.26.2"]);
+
+        let unreadable = all.iter().filter(|c| ratatui_version(c).is_none()).count();
+        assert_eq!(unreadable, 3);
+    }
+
+    #[test]
+    fn test_replay_refines_until_a_reset() {
+        let all = vec![
+            package("1", "tui-input", 10),
+            package("2", "tui-logger", 500),
+            package("3", "ratatui", 9000),
+        ];
+
+        let ops = vec![ListOp::Search("tui".into()), ListOp::MinDownloads(100)];
+        assert_eq!(names(&replay(&ops, &all)), vec!["tui-logger", "ratatui"]);
+
+        let ops = vec![ListOp::Search("input".into()), ListOp::Invert];
+        assert_eq!(names(&replay(&ops, &all)), vec!["tui-logger", "ratatui"]);
+
+        let ops = vec![ListOp::Top(2), ListOp::Reverse];
+        assert_eq!(names(&replay(&ops, &all)), vec!["tui-logger", "ratatui"]);
+
+        let ops = vec![ListOp::MaxDownloads(10), ListOp::Top(1)];
+        assert_eq!(names(&replay(&ops, &all)), vec!["ratatui"]);
+
+        let ops = vec![ListOp::Allow(vec![
+            "ratatui".into(),
+            "nope".into(),
+            "tui-input".into(),
+        ])];
+        assert_eq!(names(&replay(&ops, &all)), vec!["ratatui", "tui-input"]);
+
+        let ops = vec![ListOp::Deny(["ratatui".to_string()].into())];
+        assert_eq!(names(&replay(&ops, &all)), vec!["tui-input", "tui-logger"]);
+    }
+
+    #[test]
+    fn test_age_buckets() {
+        let now = parse_date("2025-06-01T00:00:00Z").unwrap();
+        assert_eq!(
+            AgeBucket::of("2025-05-01T12:00:00Z", now),
+            Some(AgeBucket::New)
+        );
+        assert_eq!(AgeBucket::of("2024-12-01", now), Some(AgeBucket::Recent));
+        assert_eq!(
+            AgeBucket::of("2023-01-01T00:00:00.123+02:00", now),
+            Some(AgeBucket::Mature)
+        );
+        assert_eq!(
+            AgeBucket::of("2018-01-10T10:00:00Z", now),
+            Some(AgeBucket::Old)
+        );
+        assert_eq!(AgeBucket::of("", now), None);
+        assert_eq!(AgeBucket::of("last tuesday", now), None);
+    }
+}
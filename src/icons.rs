@@ -0,0 +1,163 @@
+//! icons.rs — Icon strings used throughout the UI
+//!
+//! Every emoji/unicode marker the renderer draws lives here, so switching to plain ASCII on
+//! terminals without unicode support is a single choice made at startup.
+//
+use std::env;
+
+/// A complete set of UI markers
+#[derive(Debug)]
+pub struct Icons {
+    pub core: &'static str,
+    pub favorite: &'static str,
+    pub visited: &'static str,
+    pub community: &'static str,
+    pub crates: &'static str,
+    pub downloads: &'static str,
+    pub weekly: &'static str,
+    pub description: &'static str,
+    pub stats: &'static str,
+    pub ratatui: &'static str,
+    pub dates: &'static str,
+    pub install: &'static str,
+    pub tip: &'static str,
+    pub links: &'static str,
+    pub repo: &'static str,
+    pub docs: &'static str,
+    pub home: &'static str,
+    pub categories: &'static str,
+    pub detail: &'static str,
+    pub help: &'static str,
+    pub log: &'static str,
+    pub logo: &'static str,
+    pub navigation: &'static str,
+    pub views: &'static str,
+    pub commands: &'static str,
+    pub trophy: &'static str,
+    pub medals: [&'static str; 3],
+    pub bar: &'static str,
+    pub selected: &'static str,
+    pub refresh: &'static str,
+    pub ok: &'static str,
+    pub error: &'static str,
+    pub warning: &'static str,
+    pub arrows: &'static str,
+    pub up: &'static str,
+    pub down: &'static str,
+    pub growing: &'static str,
+    pub stable: &'static str,
+    pub spinner: &'static [&'static str],
+}
+
+pub const UNICODE: Icons = Icons {
+    core: "⭐",
+    favorite: "★",
+    visited: "·",
+    community: "🌍",
+    crates: "📦",
+    downloads: "↓",
+    weekly: "📈",
+    description: "📝",
+    stats: "📊",
+    ratatui: "🔧",
+    dates: "🕐",
+    install: "📦",
+    tip: "💡",
+    links: "🔗",
+    repo: "📁",
+    docs: "📖",
+    home: "🏠",
+    categories: "🏷️ ",
+    detail: "📋",
+    help: "❓",
+    log: "📜",
+    logo: "🐀",
+    navigation: "🎹",
+    views: "📑",
+    commands: "⚡",
+    trophy: "🏆",
+    medals: ["🥇", "🥈", "🥉"],
+    bar: "█",
+    selected: "▶ ",
+    refresh: "🔄",
+    ok: "✓",
+    error: "❌",
+    warning: "⚠",
+    arrows: "↑/↓",
+    up: "↑",
+    down: "↓",
+    growing: "▲",
+    stable: "–",
+    spinner: &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"],
+};
+
+pub const ASCII: Icons = Icons {
+    core: "*",
+    favorite: "<3",
+    visited: ".",
+    community: "@",
+    crates: "#",
+    downloads: "v",
+    weekly: "~",
+    description: ">",
+    stats: ">",
+    ratatui: ">",
+    dates: ">",
+    install: ">",
+    tip: "!",
+    links: ">",
+    repo: "-",
+    docs: "-",
+    home: "-",
+    categories: ">",
+    detail: "#",
+    help: "?",
+    log: "#",
+    logo: "",
+    navigation: ">",
+    views: ">",
+    commands: ">",
+    trophy: ">",
+    medals: ["1.", "2.", "3."],
+    bar: "#",
+    selected: "> ",
+    refresh: "~",
+    ok: "+",
+    error: "x",
+    warning: "!",
+    arrows: "up/down",
+    up: "up",
+    down: "down",
+    growing: "^",
+    stable: "-",
+    spinner: &["|", "/", "-", "\\"],
+};
+
+/// Pick the icon set for this terminal.
+///
+/// `RATCRATE_ASCII=1` forces ASCII and `RATCRATE_ASCII=0` forces unicode. Otherwise unicode
+/// is used when the locale (`LC_ALL`, `LC_CTYPE`, then `LANG`) declares UTF-8.
+pub fn detect() -> &'static Icons {
+    match env::var("RATCRATE_ASCII").as_deref() {
+        Ok("1") | Ok("true") => return &ASCII,
+        Ok("0") | Ok("false") => return &UNICODE,
+        _ => {}
+    }
+
+    if cfg!(target_os = "windows") {
+        return &UNICODE;
+    }
+
+    let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .filter_map(|var| env::var(var).ok())
+        .find(|value| !value.is_empty())
+        .unwrap_or_default()
+        .to_uppercase();
+
+    if locale.contains("UTF-8") || locale.contains("UTF8") {
+        &UNICODE
+    } else {
+        &ASCII
+    }
+}
@@ -0,0 +1,132 @@
+//! links.rs — Validating and opening a crate's URLs
+//!
+//! Dataset URLs are free-form, so anything handed to the OS opener (or copied) is first
+//! checked to be a well-formed http(s) URL.
+//
+use std::io;
+use std::process::{Command, Stdio};
+use std::thread;
+
+use url::Url;
+
+use crate::types::CratePackage;
+
+/// Which of a crate's links to use
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkKind {
+    Repository,
+    Documentation,
+    Homepage,
+}
+
+impl LinkKind {
+    pub const ALL: [LinkKind; 3] = [
+        LinkKind::Repository,
+        LinkKind::Documentation,
+        LinkKind::Homepage,
+    ];
+
+    /// Parse `repo`, `docs` or `home` (the long names work too).
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "repo" | "repository" => Some(LinkKind::Repository),
+            "docs" | "documentation" => Some(LinkKind::Documentation),
+            "home" | "homepage" => Some(LinkKind::Homepage),
+            _ => None,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            LinkKind::Repository => "repository",
+            LinkKind::Documentation => "documentation",
+            LinkKind::Homepage => "homepage",
+        }
+    }
+
+    /// The raw URL for this link, if the crate has one
+    pub fn get(self, c: &CratePackage) -> Option<&str> {
+        match self {
+            LinkKind::Repository => c.repository.as_deref(),
+            LinkKind::Documentation => c.documentation.as_deref(),
+            LinkKind::Homepage => c.homepage.as_deref(),
+        }
+    }
+}
+
+/// Parse `raw` and require an http(s) URL with a host.
+///
+/// # Errors
+/// Returns a short reason suitable for the status bar.
+pub fn validate(raw: &str) -> Result<Url, String> {
+    let url = Url::parse(raw.trim()).map_err(|e| format!("'{}' ({})", raw, e))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(format!("'{}' is not an http(s) link", raw));
+    }
+    if url.host_str().is_none_or(str::is_empty) {
+        return Err(format!("'{}' has no host", raw));
+    }
+    Ok(url)
+}
+
+/// The command that opens `url` on `os` (as in `std::env::consts::OS`).
+///
+/// The URL is always a single argument to a program that takes it literally. On Windows
+/// that rules out `cmd /C start`: cmd.exe would run whatever follows a `&` in the query.
+fn opener(os: &str, url: &Url) -> Command {
+    let program = match os {
+        "macos" => "open",
+        "windows" => "explorer.exe",
+        _ => "xdg-open",
+    };
+    let mut command = Command::new(program);
+    command.arg(url.as_str());
+    command
+}
+
+/// Open `url` with the platform's default handler.
+///
+/// Returns once the opener is spawned; a background thread reaps it.
+pub fn open(url: &Url) -> io::Result<()> {
+    // Keep the opener's output from scribbling over the TUI
+    let mut child = opener(std::env::consts::OS, url)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    thread::spawn(move || child.wait());
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Unit tests for links.rs
+// ---------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_accepts_only_http_urls() {
+        assert!(validate("https://github.com/ratatui/ratatui").is_ok());
+        assert!(validate(" http://docs.rs/ratatui ").is_ok());
+        assert!(validate("github.com/ratatui/ratatui").is_err());
+        assert!(validate("javascript:alert(1)").is_err());
+        assert!(validate("file:///etc/passwd").is_err());
+        assert!(validate("").is_err());
+    }
+
+    #[test]
+    fn test_opener_passes_the_url_as_one_argument() {
+        let url = validate("https://example.com/x?a=1&calc.exe|b^c").unwrap();
+        for (os, program) in [
+            ("windows", "explorer.exe"),
+            ("macos", "open"),
+            ("linux", "xdg-open"),
+        ] {
+            let command = opener(os, &url);
+            assert_eq!(command.get_program(), program);
+            let args: Vec<_> = command.get_args().collect();
+            assert_eq!(args, [url.as_str()]);
+        }
+    }
+}
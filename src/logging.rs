@@ -0,0 +1,115 @@
+//! logging.rs — Optional troubleshooting log written to a file
+//!
+//! The TUI owns the terminal, so anything on stderr is lost. Setting `RATCRATE_LOG=<path>`
+//! appends timestamped records of data-load decisions (cache hits, staleness, downloads,
+//! parse timings, errors) to that file instead. Without it no logger is installed and the
+//! `log` macros stop at a level check.
+//
+use std::env;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use log::{LevelFilter, Log, Metadata, Record};
+
+/// Environment variable naming the log file
+pub const LOG_ENV: &str = "RATCRATE_LOG";
+
+struct FileLogger {
+    file: Mutex<File>,
+}
+
+impl Log for FileLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        // Only our own records; dependencies such as reqwest log at debug too
+        metadata.target().starts_with(env!("CARGO_CRATE_NAME"))
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let line = format_record(
+            &Utc::now().format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string(),
+            record,
+        );
+        if let Ok(mut file) = self.file.lock() {
+            let _ = file.write_all(line.as_bytes());
+        }
+    }
+
+    fn flush(&self) {
+        if let Ok(mut file) = self.file.lock() {
+            let _ = file.flush();
+        }
+    }
+}
+
+/// `<timestamp> <LEVEL> <module>: <message>`, with the crate prefix dropped from the module
+fn format_record(timestamp: &str, record: &Record) -> String {
+    let target = record.target();
+    let module = target
+        .strip_prefix(concat!(env!("CARGO_CRATE_NAME"), "::"))
+        .unwrap_or(target);
+    format!(
+        "{} {:<5} {}: {}\n",
+        timestamp,
+        record.level(),
+        module,
+        record.args()
+    )
+}
+
+/// Install the file logger when `RATCRATE_LOG` is set. Returns the log file path, if any.
+///
+/// # Errors
+/// Returns an error if the log file cannot be opened for appending.
+pub fn init() -> Result<Option<PathBuf>> {
+    let Some(path) = env::var_os(LOG_ENV).filter(|p| !p.is_empty()) else {
+        return Ok(None);
+    };
+    let path = PathBuf::from(path);
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("Failed to open log file {}", path.display()))?;
+
+    log::set_boxed_logger(Box::new(FileLogger {
+        file: Mutex::new(file),
+    }))
+    .context("A logger is already installed")?;
+    log::set_max_level(LevelFilter::Debug);
+    log::info!(
+        "{} {} started, logging to {}",
+        env!("CARGO_PKG_NAME"),
+        env!("CARGO_PKG_VERSION"),
+        path.display()
+    );
+    Ok(Some(path))
+}
+
+// ---------------------------------------------------------------------------
+// Unit tests for logging.rs
+// ---------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_record_strips_the_crate_prefix() {
+        let target = concat!(env!("CARGO_CRATE_NAME"), "::cache");
+        let line = format_record(
+            "2025-12-10T08:00:00.000Z",
+            &Record::builder()
+                .level(log::Level::Info)
+                .target(target)
+                .args(format_args!("cache hit"))
+                .build(),
+        );
+        assert_eq!(line, "2025-12-10T08:00:00.000Z INFO  cache: cache hit\n");
+    }
+}
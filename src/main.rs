@@ -4,25 +4,132 @@
 
 use anyhow::Result;
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyModifiers},
+    event::{
+        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyModifiers,
+    },
     execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
 use ratatui::{
     Frame, Terminal,
     backend::CrosstermBackend,
-    layout::{Constraint, Direction, Layout, Rect},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
+    symbols,
     text::{Line, Span, Text},
-    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
+    widgets::{
+        Bar, BarChart, BarGroup, Block, Clear, Gauge, List, ListItem, ListState, Paragraph, Wrap,
+    },
 };
-use std::io;
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fs;
+use std::io::{self, IsTerminal, Write};
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+use std::time::{Duration, Instant};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 mod cache;
+mod cli;
+mod clipboard;
+mod config;
+mod favorites;
+mod filter;
+mod icons;
+mod links;
+mod logging;
+mod session;
+mod sort;
+mod theme;
+mod tryout;
 mod types;
 
-use cache::get_data;
-use types::{CratePackage, CratesData};
+use cache::{DataOrigin, Provenance, get_data};
+use clipboard::Clipboard;
+use config::{Config, UiConfig};
+use favorites::{Favorite, Favorites};
+use filter::{AgeBucket, ListOp};
+use icons::Icons;
+use links::LinkKind;
+use session::Session;
+use sort::{SortOrder, SortStep};
+use theme::Theme;
+use tryout::Plan;
+use types::{CratePackage, CratesData, RatatuiDependency, find_duplicates};
+
+/// How many status messages the log pane keeps
+const STATUS_LOG_CAPACITY: usize = 200;
+/// How many earlier lists `:prev` can step back through
+const LIST_HISTORY_DEPTH: usize = 20;
+/// Where `:export md` writes when no path is given
+const EXPORT_MD_PATH: &str = "./ratcrate-export.md";
+/// Command names offered by Tab completion
+const COMMANDS: &[&str] = &[
+    "about",
+    "add",
+    "age",
+    "all",
+    "allowlist",
+    "badges",
+    "cacheinfo",
+    "categories",
+    "category",
+    "clear-cache",
+    "cleardeltas",
+    "compat",
+    "copylist",
+    "copyview",
+    "core",
+    "count",
+    "denylist",
+    "desc",
+    "export",
+    "fav",
+    "favs",
+    "goto",
+    "help",
+    "invert",
+    "maxdl",
+    "mindl",
+    "mutecore",
+    "new",
+    "newsince",
+    "nodocs",
+    "nohome",
+    "open",
+    "prev",
+    "quit",
+    "raw",
+    "recent",
+    "refresh",
+    "report",
+    "reset",
+    "search",
+    "sort",
+    "tags",
+    "top",
+    "try",
+    "viewed",
+];
+/// Width of the overview/downloads column in the two-column stats layout
+const STATS_LEFT_COLUMN_WIDTH: u16 = 36;
+/// Narrowest useful right column: a top 5 label (medal, 20-column name, 10-column age), a
+/// space, and a bar long enough to hold a 10-column download count
+const STATS_RIGHT_COLUMN_MIN_WIDTH: u16 = 3 + 20 + 10 + 1 + 10;
+/// Stats pane inner width at which it splits into two columns
+const STATS_TWO_COLUMN_MIN_WIDTH: u16 = STATS_LEFT_COLUMN_WIDTH + STATS_RIGHT_COLUMN_MIN_WIDTH;
+/// Height of the expanded log pane, including borders
+const LOG_PANE_HEIGHT: u16 = 12;
+/// Width between the borders of the help/stats banners
+const BANNER_INNER_WIDTH: usize = 55;
+/// Poll interval while something animates (the busy spinner), in milliseconds
+const ANIMATION_POLL_MS: u64 = 100;
+/// Most browser tabs `:open all` will spawn at once
+const MAX_OPEN_LINKS: usize = 3;
+/// Days without an update after which a crate is shown as stale
+const STALE_AFTER_DAYS: i64 = 365;
 
 // ============================================================================
 // App State
@@ -30,41 +137,181 @@ use types::{CratePackage, CratesData};
 
 #[derive(Debug, Clone, PartialEq)]
 enum Mode {
-    Normal, // Navigation mode
+    Normal,  // Navigation mode
     Command, // Command mode (after pressing ':')
-            // Try,         // Try mode - confirming installation
+    Search,  // Incremental search (after pressing '/')
+    Confirm, // Waiting for y/n on a pending action
+    Jump,    // Waiting for a letter to jump to in the name-sorted list
+}
+
+/// An action that needs a y/n confirmation in the command bar before it runs
+#[derive(Debug, Clone, PartialEq)]
+enum PendingAction {
+    /// Clear the current filter, then select the named crate
+    GotoClearingFilter(String),
+    /// Delete saved state and restore settings; `all` also deletes the data cache
+    Reset { all: bool },
+    /// Run the cargo commands of `:try` or `:add`
+    Run(Plan),
 }
 
 #[derive(Debug, Clone, PartialEq)]
 enum View {
-    List,  // List + Detail view
-    Stats, // Statistics view
-    Help,  // Help view
+    List,       // List + Detail view
+    Stats,      // Statistics view
+    Help,       // Help view
+    Categories, // Category browser
+}
+
+/// How long a crate must stay selected to count as viewed
+const VIEW_DWELL: Duration = Duration::from_millis(1500);
+/// How many recently viewed crates are remembered
+const RECENTLY_VIEWED_CAPACITY: usize = 9;
+
+/// Label of the category-browser row for crates without categories
+const UNCATEGORIZED: &str = "Uncategorized";
+
+/// A centered overlay with a title and styled lines; any key dismisses it.
+///
+/// When `picks` is not empty, line `i` describes pick `i`: j/k move the selection, and
+/// Enter or the digit keys 1-9 act on a pick.
+#[derive(Debug, Clone)]
+struct Popup {
+    title: String,
+    lines: Vec<Line<'static>>,
+    picks: Vec<Pick>,
+    selected: usize,
+    /// Top line of a scrollable text popup (j/k scroll, Esc closes); `None` closes on any key
+    scroll: Option<usize>,
+}
+
+/// Something a picker popup can act on
+#[derive(Debug, Clone, PartialEq)]
+enum Pick {
+    /// Jump to the crate with this id
+    Crate(String),
+    /// Filter the list to this normalized category
+    Category(String),
+}
+
+impl Popup {
+    /// A popup of plain, indented text lines.
+    fn text(title: &str, lines: Vec<String>) -> Self {
+        Self {
+            title: title.to_string(),
+            lines: lines
+                .into_iter()
+                .map(|l| Line::from(Span::raw(format!("  {}", l))))
+                .collect(),
+            picks: vec![],
+            selected: 0,
+            scroll: None,
+        }
+    }
+
+    /// Like `text`, but long contents scroll and only Esc (or q) closes it.
+    fn scrolling(title: &str, lines: Vec<String>) -> Self {
+        Self {
+            scroll: Some(0),
+            ..Self::text(title, lines)
+        }
+    }
 }
 
 struct App {
     // Data
     all_crates: Vec<CratePackage>,
     filtered_crates: Vec<CratePackage>,
+    filtered_downloads: u64, // Sum of `downloads` over `filtered_crates`
     metadata: types::Metadata,
+    id_index: HashMap<String, usize>, // id -> first index in all_crates
+    category_counts: HashMap<String, usize>, // normalized category -> crates in all_crates
+    category_labels: HashMap<String, String>, // normalized category -> first spelling seen
+    categories: Vec<(Option<String>, usize)>, // Category browser rows, `None` = uncategorized
 
     // UI State
     list_state: ListState,
+    category_state: ListState,
     mode: Mode,
     view: View,
     command_input: String,
+    search_preview: Option<usize>, // Live match count while typing `:search <query>`
+    search_origin: Option<(Vec<ListOp>, Option<usize>)>, // List and selection before `/`
+    command_history: Vec<String>,  // Executed commands, oldest first
+    history_cursor: usize,         // Position while recalling with Up/Down; len() = new input
+    completion: Option<(&'static str, Vec<String>, usize)>, // Tab prefix, candidates, current
     status_message: String,
+    status_log: VecDeque<(String, String)>, // (timestamp, message), oldest first
+    show_log: bool,
+    log_scroll: usize, // Lines scrolled up from the newest entry
 
-    // Try mode
-    // try_crate: Option<String>,
-    // try_temp_dir: Option<String>,
+    // `:try` / `:add` commands running in the background
+    plan_rx: Option<Receiver<Result<PathBuf>>>,
 
     // Search state
     last_search: String,
+
+    // Steps that produced `filtered_crates`, and earlier step chains for `:prev`
+    list_ops: Vec<ListOp>,
+    list_history: Vec<Vec<ListOp>>,
+    default_sort: Option<SortOrder>, // From `ui.default_sort`, applied to the startup list
+
+    // Starred crates, and the list to return to when leaving the favorites-only view
+    favorites: Favorites,
+    favorites_return: Option<Vec<ListOp>>,
+
+    // Crates that stayed selected for a moment (ids, most recent first), and the current one
+    recently_viewed: Vec<String>,
+    viewing: Option<(String, Instant)>,
+    visited: HashSet<String>, // Every crate viewed this session, for the gutter
+
+    // Confirmation prompt
+    pending_action: Option<PendingAction>,
+
+    // Whether entering Jump mode had to sort the list by name first
+    jump_sorted: bool,
+
+    // Overlay shown above the current view
+    popup: Option<Popup>,
+
+    // System clipboard, opened on first copy
+    clipboard: Clipboard,
+
+    // Background work: the refresh channel, and how many tasks are running in total
+    refresh_rx: Option<Receiver<Result<CratesData>>>,
+    provenance: Option<Provenance>, // Where the current data came from, for the command bar
+    persistent: bool,               // False when saved state lands in a temporary directory
+    offline: bool,                  // `--offline`: never download, not even on :refresh
+    download_deltas: HashMap<String, i64>, // id -> downloads gained in the last refresh
+    busy: usize,
+    tick: usize,
+
+    // Rendering
+    icons: &'static Icons,
+    theme: Theme,
+    compact_list: bool,       // One line per crate instead of two
+    gutter: bool,             // Marker column for favorites and visited crates
+    count_as_percent: bool,   // List title shows the matched share instead of matched/total
+    mute_core: bool,          // Render core libraries like any other crate
+    trend_markers: bool,      // Growing/stable marker after each crate's download stats
+    select_top_result: bool,  // Filters select their most downloaded match, not the first row
+    scroll_margin: usize,     // Rows kept visible above/below the selection
+    description_lines: usize, // Description lines shown when not expanded; 0 = no limit
+    full_description: bool,   // Show the whole description regardless of the limit
+    poll_interval: Duration,  // Idle wait for input between redraws
+    ui_config: UiConfig,      // Startup `[ui]` settings, restored by `:reset`
 }
 
 impl App {
-    fn new(data: CratesData) -> Self {
+    fn new(
+        data: CratesData,
+        icons: &'static Icons,
+        theme: Theme,
+        config: &Config,
+        session: &Session,
+        favorites: Favorites,
+        initial_search: Option<&str>,
+    ) -> Self {
         let mut list_state = ListState::default();
         list_state.select(Some(0));
 
@@ -72,21 +319,417 @@ impl App {
         let filtered_crates = all_crates.clone();
         let metadata = data.metadata.clone();
 
-        Self {
+        let mut app = Self {
             all_crates,
             filtered_crates,
+            filtered_downloads: 0,
             metadata: metadata.clone(),
+            id_index: HashMap::new(),
+            category_counts: HashMap::new(),
+            category_labels: HashMap::new(),
+            categories: vec![],
+            category_state: ListState::default(),
             list_state,
             mode: Mode::Normal,
             view: View::List,
             command_input: String::new(),
-            status_message: format!(
-                "📦 {} crates | ⭐ {} core | 🌍 {} community | Press TAB for stats, ? for help, : for commands",
-                metadata.total_crates, metadata.core_libraries, metadata.community_packages
-            ),
-            // try_crate: None,
-            // try_temp_dir: None,
+            search_preview: None,
+            search_origin: None,
+            command_history: Vec::new(),
+            history_cursor: 0,
+            completion: None,
+            status_message: String::new(),
+            status_log: VecDeque::new(),
+            show_log: false,
+            log_scroll: 0,
+            plan_rx: None,
             last_search: String::new(),
+            list_ops: vec![],
+            list_history: vec![],
+            default_sort: None,
+            favorites,
+            favorites_return: None,
+            recently_viewed: session.recently_viewed.clone(),
+            visited: HashSet::new(),
+            viewing: None,
+            pending_action: None,
+            jump_sorted: false,
+            popup: None,
+            clipboard: Clipboard::default(),
+            refresh_rx: None,
+            provenance: None,
+            persistent: true,
+            offline: false,
+            download_deltas: HashMap::new(),
+            busy: 0,
+            tick: 0,
+            icons,
+            theme,
+            compact_list: config.ui.compact_list,
+            gutter: config.ui.gutter,
+            count_as_percent: false,
+            mute_core: config.ui.mute_core,
+            trend_markers: config.ui.trend_markers,
+            select_top_result: config.ui.select_top_result,
+            scroll_margin: config.ui.scroll_margin,
+            description_lines: config.ui.description_lines,
+            full_description: false,
+            ui_config: config.ui.clone(),
+            poll_interval: Duration::from_millis(config.ui.poll_interval_ms.max(1)),
+        };
+        app.set_status(format!(
+            "{} {} crates | {} {} core | {} {} community | Press TAB for stats, ? for help, : for commands",
+            icons.crates,
+            metadata.total_crates,
+            icons.core,
+            metadata.core_libraries,
+            icons.community,
+            metadata.community_packages
+        ));
+        app.index_crates();
+
+        if let Some(spec) = &config.ui.default_sort {
+            match SortOrder::parse(spec) {
+                Ok(order) => app.default_sort = Some(order),
+                Err(e) => app.set_status(format!(
+                    "{} ui.default_sort: {}; keeping dataset order",
+                    icons.warning, e
+                )),
+            }
+        }
+        app.reset_list();
+
+        // Pick up where the last session left off, if that crate is still around
+        if let Some(id) = &session.selected_id {
+            app.select_id(id);
+        }
+        if let Some(previous) = &session.data_version
+            && *previous != app.metadata.version
+        {
+            app.show_data_update(previous, session.crate_count);
+        }
+
+        // `ratcrate-tui <query>` opens straight into a search
+        if let Some(query) = initial_search {
+            let query = query.to_lowercase();
+            if app
+                .all_crates
+                .iter()
+                .any(|c| filter::fuzzy_score(c, &query).is_some())
+            {
+                app.last_search = query.clone();
+                app.apply_list_op(ListOp::Fuzzy(query));
+                app.set_status(format!(
+                    "Found {} crates matching '{}'",
+                    app.filtered_crates.len(),
+                    app.last_search
+                ));
+            } else {
+                app.set_status(format!("No crates match '{}'; showing all crates", query));
+            }
+        }
+        app
+    }
+
+    /// Pop up a note that the dataset changed version since the last session.
+    fn show_data_update(&mut self, previous_version: &str, previous_count: Option<usize>) {
+        let count = self.all_crates.len();
+        let mut lines = vec![format!(
+            "Data updated: v{} -> v{}",
+            previous_version, self.metadata.version
+        )];
+        if let Some(previous) = previous_count {
+            let delta = count as i64 - previous as i64;
+            lines.push(format!("Crates: {} -> {} ({:+})", previous, count, delta));
+        }
+
+        self.set_status(format!("Data updated to v{}", self.metadata.version));
+        self.popup = Some(Popup::text("What's New", lines));
+    }
+
+    /// Show the startup list: every crate, in the configured default order.
+    fn reset_list(&mut self) {
+        self.list_ops = self.default_sort.map(ListOp::Sort).into_iter().collect();
+        self.set_filtered(filter::replay(&self.list_ops, &self.all_crates));
+    }
+
+    /// Replace the visible list and refresh the totals derived from it.
+    fn set_filtered(&mut self, crates: Vec<CratePackage>) {
+        self.filtered_downloads = crates.iter().map(|c| c.downloads).sum();
+        self.filtered_crates = crates;
+    }
+
+    /// Whether the list is narrowed by anything other than a sort or reversal
+    fn is_filtered(&self) -> bool {
+        self.list_ops
+            .iter()
+            .any(|op| !matches!(op, ListOp::Sort(_) | ListOp::Reverse))
+    }
+
+    /// The sort applied to the current list, if any
+    fn active_sort(&self) -> Option<SortOrder> {
+        self.list_ops.iter().rev().find_map(|op| match op {
+            ListOp::Sort(order) => Some(*order),
+            _ => None,
+        })
+    }
+
+    /// Select the crate with this id in the current list. Returns false if it is not shown.
+    fn select_id(&mut self, id: &str) -> bool {
+        match self.filtered_crates.iter().position(|c| c.id == id) {
+            Some(i) => {
+                self.list_state.select(Some(i));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Capture the state worth restoring on the next launch.
+    fn to_session(&self) -> Session {
+        Session {
+            selected_id: self.selected_crate().map(|c| c.id.clone()),
+            data_version: Some(self.metadata.version.clone()),
+            crate_count: Some(self.all_crates.len()),
+            recently_viewed: self.recently_viewed.clone(),
+        }
+    }
+
+    /// Rebuild the id lookup and category counts, and warn once if the dataset has duplicate
+    /// ids or names.
+    ///
+    /// Duplicates are kept in the list; id lookups resolve to the first occurrence.
+    fn index_crates(&mut self) {
+        self.id_index.clear();
+        self.category_counts.clear();
+        self.category_labels.clear();
+        for (i, c) in self.all_crates.iter().enumerate() {
+            self.id_index.entry(c.id.clone()).or_insert(i);
+            // Count by normalized name so `CLI` and `cli` are one category
+            for cat in filter::categories_of(c) {
+                *self.category_counts.entry(cat).or_default() += 1;
+            }
+            for cat in c.categories.iter().flatten() {
+                self.category_labels
+                    .entry(filter::normalize_category(cat))
+                    .or_insert_with(|| cat.trim().to_string());
+            }
+        }
+
+        // Most used first, ties by name, with the uncategorized bucket last
+        let mut categories: Vec<(Option<String>, usize)> = self
+            .category_counts
+            .iter()
+            .map(|(cat, n)| (Some(cat.clone()), *n))
+            .collect();
+        categories.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        let uncategorized = self
+            .all_crates
+            .iter()
+            .filter(|c| filter::has_category(c, None))
+            .count();
+        if uncategorized > 0 {
+            categories.push((None, uncategorized));
+        }
+        self.categories = categories;
+        self.category_state
+            .select((!self.categories.is_empty()).then_some(0));
+        self.favorites.resolve(&self.all_crates);
+
+        let dups = find_duplicates(&self.all_crates);
+        if !dups.is_empty() {
+            let mut parts = vec![];
+            if !dups.ids.is_empty() {
+                parts.push(format!("ids {}", summarize(&dups.ids)));
+            }
+            if !dups.names.is_empty() {
+                parts.push(format!("names {}", summarize(&dups.names)));
+            }
+            self.set_status(format!(
+                "{} Dataset has duplicate {}",
+                self.icons.warning,
+                parts.join(", ")
+            ));
+        }
+    }
+
+    /// Show a message in the status bar and record it in the log pane.
+    fn set_status(&mut self, message: impl Into<String>) {
+        self.status_message = message.into();
+
+        if self.status_log.len() == STATUS_LOG_CAPACITY {
+            self.status_log.pop_front();
+        }
+        let timestamp = chrono::Local::now().format("%H:%M:%S").to_string();
+        self.status_log
+            .push_back((timestamp, self.status_message.clone()));
+    }
+
+    fn toggle_log(&mut self) {
+        self.show_log = !self.show_log;
+        self.log_scroll = 0;
+    }
+
+    fn scroll_log_up(&mut self, lines: usize) {
+        self.log_scroll = (self.log_scroll + lines).min(self.status_log.len().saturating_sub(1));
+    }
+
+    fn scroll_log_down(&mut self, lines: usize) {
+        self.log_scroll = self.log_scroll.saturating_sub(lines);
+    }
+
+    fn is_refreshing(&self) -> bool {
+        self.refresh_rx.is_some()
+    }
+
+    fn spinner(&self) -> &'static str {
+        self.icons.spinner[self.tick % self.icons.spinner.len()]
+    }
+
+    /// Note that a background task started; the busy indicator shows until it ends.
+    fn begin_task(&mut self) {
+        self.busy += 1;
+    }
+
+    fn end_task(&mut self) {
+        self.busy = self.busy.saturating_sub(1);
+    }
+
+    /// Start downloading fresh data on a background thread.
+    ///
+    /// The current data stays visible and navigable until `poll_refresh` swaps it out.
+    fn start_refresh(&mut self) {
+        if self.is_refreshing() {
+            self.set_status("Refresh already in progress");
+            return;
+        }
+        if self.offline {
+            self.set_status("Offline: restart without --offline to download fresh data");
+            return;
+        }
+
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let _ = tx.send(cache::fetch_remote_data());
+        });
+        self.refresh_rx = Some(rx);
+        self.begin_task();
+        self.set_status(format!(
+            "{} Refreshing data in the background...",
+            self.icons.refresh
+        ));
+    }
+
+    /// Delete the cached dataset and its ETag, then download it again in the background, for
+    /// when the cache got corrupted. A file that is already gone counts as deleted.
+    fn clear_cache(&mut self) {
+        if self.offline {
+            self.set_status("Offline: restart without --offline to clear and refetch the cache");
+            return;
+        }
+        if self.is_refreshing() {
+            self.set_status("Refresh already in progress");
+            return;
+        }
+
+        for path in [cache::get_cache_file(), cache::get_etag_file()] {
+            let removed = path.and_then(|path| match fs::remove_file(&path) {
+                Err(e) if e.kind() != io::ErrorKind::NotFound => {
+                    Err(anyhow::Error::new(e).context(format!("Cannot delete {}", path.display())))
+                }
+                _ => Ok(()),
+            });
+            if let Err(e) = removed {
+                self.set_status(format!("{} {:#}", self.icons.error, e));
+                return;
+            }
+        }
+        self.start_refresh();
+        self.set_status(format!(
+            "{} Cache cleared; downloading fresh data in the background...",
+            self.icons.refresh
+        ));
+    }
+
+    /// Check whether a background refresh has finished and, if so, swap in the new data.
+    fn poll_refresh(&mut self) {
+        let Some(rx) = &self.refresh_rx else {
+            return;
+        };
+
+        match rx.try_recv() {
+            Ok(Ok(data)) => {
+                self.refresh_rx = None;
+                self.end_task();
+                self.set_status(format!(
+                    "{} Refreshed: {} crates loaded",
+                    self.icons.ok, data.metadata.total_crates
+                ));
+                self.download_deltas = download_deltas(&self.all_crates, &data.crates);
+                // Swapping last lets a duplicate warning win the status bar
+                self.replace_data(data);
+                self.provenance = Some(Provenance::downloaded());
+            }
+            Ok(Err(e)) => {
+                log::warn!("refresh failed: {:#}", e);
+                self.refresh_rx = None;
+                self.end_task();
+                self.set_status(format!("{} Refresh failed: {}", self.icons.error, e));
+            }
+            Err(TryRecvError::Empty) => {}
+            Err(TryRecvError::Disconnected) => {
+                self.refresh_rx = None;
+                self.end_task();
+                self.set_status(format!(
+                    "{} Refresh failed: worker exited unexpectedly",
+                    self.icons.error
+                ));
+            }
+        }
+    }
+
+    /// A "↑ +12K" marker for a crate whose downloads changed in the last refresh.
+    fn download_delta_span(&self, id: &str) -> Option<Span<'static>> {
+        let delta = *self.download_deltas.get(id)?;
+        let (icon, sign, color) = if delta > 0 {
+            (self.icons.up, '+', Color::Green)
+        } else {
+            (self.icons.down, '-', Color::Red)
+        };
+        Some(Span::styled(
+            format!(" {} {}{}", icon, sign, format_number(delta.unsigned_abs())),
+            Style::default().fg(color),
+        ))
+    }
+
+    /// Replace the dataset in one step, replaying the current filters and search over it and
+    /// keeping the selected crate (by id, else by name) when it is still listed.
+    fn replace_data(&mut self, data: CratesData) {
+        let selected = self
+            .selected_crate()
+            .map(|c| (c.id.clone(), c.name.clone()));
+
+        self.all_crates = data.crates;
+        self.metadata = data.metadata;
+        self.index_crates();
+        // Favorites matched by name may have new ids
+        if let Some(ListOp::Favorites(ids)) = self.list_ops.first_mut() {
+            *ids = self.favorites.ids();
+        }
+        self.set_filtered(filter::replay(&self.list_ops, &self.all_crates));
+
+        let kept = selected.is_some_and(|(id, name)| {
+            self.select_id(&id)
+                || match self.filtered_crates.iter().position(|c| c.name == name) {
+                    Some(i) => {
+                        self.list_state.select(Some(i));
+                        true
+                    }
+                    None => false,
+                }
+        });
+        if !kept {
+            self.reset_selection();
         }
     }
 
@@ -96,828 +739,3073 @@ impl App {
             .and_then(|i| self.filtered_crates.get(i))
     }
 
-    fn next(&mut self) {
-        let i = match self.list_state.selected() {
-            Some(i) => {
-                if i >= self.filtered_crates.len() - 1 {
-                    0
-                } else {
-                    i + 1
+    /// Select the crate whose name matches `name` exactly (ignoring case).
+    ///
+    /// Only the current `filtered_crates` are searched. If the crate exists but is hidden
+    /// by the filter, ask before clearing it.
+    fn goto(&mut self, name: &str) {
+        if let Some(i) = self
+            .filtered_crates
+            .iter()
+            .position(|c| c.name.eq_ignore_ascii_case(name))
+        {
+            self.list_state.select(Some(i));
+            self.set_status(format!("Jumped to '{}'", self.filtered_crates[i].name));
+        } else if let Some(found) = self
+            .all_crates
+            .iter()
+            .find(|c| c.name.eq_ignore_ascii_case(name))
+            .map(|c| c.name.clone())
+        {
+            self.set_status(format!(
+                "'{}' is hidden by the current filter. Clear filter and jump to it? (y/n)",
+                found
+            ));
+            self.pending_action = Some(PendingAction::GotoClearingFilter(found));
+            self.mode = Mode::Confirm;
+        } else {
+            self.set_status(format!("No crate named '{}'", name));
+        }
+    }
+
+    /// Remember the selected crate as viewed once it has stayed selected for `VIEW_DWELL`.
+    fn track_viewed(&mut self, now: Instant) {
+        let Some(id) = self.selected_crate().map(|c| c.id.clone()) else {
+            self.viewing = None;
+            return;
+        };
+        match &self.viewing {
+            Some((viewing, since)) if *viewing == id => {
+                if now.duration_since(*since) >= VIEW_DWELL
+                    && self.recently_viewed.first() != Some(&id)
+                {
+                    self.visited.insert(id.clone());
+                    self.recently_viewed.retain(|v| *v != id);
+                    self.recently_viewed.insert(0, id);
+                    self.recently_viewed.truncate(RECENTLY_VIEWED_CAPACITY);
                 }
             }
-            None => 0,
+            _ => self.viewing = Some((id, now)),
+        }
+    }
+
+    /// Pop up the recently viewed crates; a digit key jumps to one.
+    fn show_recently_viewed(&mut self) {
+        let entries: Vec<&CratePackage> = self
+            .recently_viewed
+            .iter()
+            .filter_map(|id| self.id_index.get(id).map(|&i| &self.all_crates[i]))
+            .collect();
+        if entries.is_empty() {
+            self.set_status("No recently viewed crates yet");
+            return;
+        }
+
+        let lines = entries
+            .iter()
+            .enumerate()
+            .map(|(i, c)| {
+                Line::from(vec![
+                    Span::styled(format!("  {} ", i + 1), Style::default().fg(Color::Yellow)),
+                    Span::styled(c.name.clone(), Style::default().fg(Color::Cyan)),
+                ])
+            })
+            .collect();
+        let picks = entries.iter().map(|c| Pick::Crate(c.id.clone())).collect();
+        self.popup = Some(Popup {
+            title: "Recently Viewed".to_string(),
+            lines,
+            picks,
+            selected: 0,
+            scroll: None,
+        });
+    }
+
+    /// Pop up every category of the selected crate; Enter or a digit filters by one.
+    fn show_crate_categories(&mut self) {
+        let Some(crate_pkg) = self.selected_crate() else {
+            self.set_status("No crate selected");
+            return;
         };
-        self.list_state.select(Some(i));
+
+        // One row per normalized category, shown with the crate's own spelling
+        let mut picks = vec![];
+        let mut lines = vec![];
+        for cat in crate_pkg.categories.iter().flatten() {
+            let key = filter::normalize_category(cat);
+            if key.is_empty() || picks.contains(&Pick::Category(key.clone())) {
+                continue;
+            }
+            let number = if picks.len() < 9 {
+                format!("  {} ", picks.len() + 1)
+            } else {
+                "    ".to_string()
+            };
+            lines.push(Line::from(vec![
+                Span::styled(number, Style::default().fg(Color::Yellow)),
+                Span::styled(cat.trim().to_string(), Style::default().fg(Color::Cyan)),
+                Span::styled(
+                    format!(
+                        " ({})",
+                        self.category_counts.get(&key).copied().unwrap_or(0)
+                    ),
+                    Style::default().fg(Color::Green),
+                ),
+            ]));
+            picks.push(Pick::Category(key));
+        }
+        if picks.is_empty() {
+            self.set_status(format!("{} has no categories", crate_pkg.name));
+            return;
+        }
+
+        self.popup = Some(Popup {
+            title: format!("Categories of {}", crate_pkg.name),
+            lines,
+            picks,
+            selected: 0,
+            scroll: None,
+        });
     }
 
-    fn previous(&mut self) {
-        let i = match self.list_state.selected() {
-            Some(i) => {
-                if i == 0 {
-                    self.filtered_crates.len() - 1
-                } else {
-                    i - 1
-                }
+    /// Act on an entry chosen in a picker popup.
+    fn activate_pick(&mut self, pick: Pick) {
+        match pick {
+            Pick::Crate(id) => self.jump_to_id(&id),
+            Pick::Category(category) => {
+                self.apply_list_op(ListOp::Category(Some(category.clone())));
+                self.view = View::List;
+                self.set_status(format!(
+                    "Showing {} crates in {}",
+                    self.filtered_crates.len(),
+                    self.category_label(Some(&category))
+                ));
             }
-            None => 0,
-        };
-        self.list_state.select(Some(i));
+        }
     }
 
-    fn next_page(&mut self) {
-        let jump = 10;
-        let i = match self.list_state.selected() {
-            Some(i) => {
-                if i + jump >= self.filtered_crates.len() {
-                    self.filtered_crates.len() - 1
-                } else {
-                    i + jump
-                }
+    /// Select the crate with this id, clearing the filter first if it hides the crate.
+    fn jump_to_id(&mut self, id: &str) {
+        if !self.select_id(id) {
+            self.apply_list_op(ListOp::All);
+            self.last_search.clear();
+            self.select_id(id);
+        }
+        if let Some(name) = self.selected_crate().map(|c| c.name.clone()) {
+            self.set_status(format!("Jumped to '{}'", name));
+        }
+    }
+
+    /// Run the pending action after the user pressed 'y'.
+    fn confirm_pending(&mut self) {
+        self.mode = Mode::Normal;
+        match self.pending_action.take() {
+            Some(PendingAction::GotoClearingFilter(name)) => {
+                self.apply_list_op(ListOp::All);
+                self.last_search.clear();
+                self.goto(&name);
             }
-            None => 0,
+            Some(PendingAction::Reset { all }) => self.reset_state(all),
+            Some(PendingAction::Run(plan)) => self.start_plan(plan),
+            None => {}
+        }
+    }
+
+    /// Show exactly what `plan` will run, and where, before running it.
+    fn confirm_plan(&mut self, plan: Plan) {
+        if self.plan_rx.is_some() {
+            self.set_status("A cargo command is already running");
+            return;
+        }
+        self.set_status(format!("Run: {} ? (y/n)", plan.describe()));
+        self.pending_action = Some(PendingAction::Run(plan));
+        self.mode = Mode::Confirm;
+    }
+
+    /// Run a confirmed plan on a background thread; `poll_plan` reports the outcome.
+    fn start_plan(&mut self, plan: Plan) {
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let _ = tx.send(plan.run());
+        });
+        self.plan_rx = Some(rx);
+        self.begin_task();
+        self.set_status(format!("{} Running cargo...", self.icons.refresh));
+    }
+
+    /// Report a finished `:try` / `:add`.
+    fn poll_plan(&mut self) {
+        let Some(rx) = &self.plan_rx else {
+            return;
         };
-        self.list_state.select(Some(i));
+
+        let result = match rx.try_recv() {
+            Ok(result) => result,
+            Err(TryRecvError::Empty) => return,
+            Err(TryRecvError::Disconnected) => Err(anyhow::anyhow!("worker exited unexpectedly")),
+        };
+        self.plan_rx = None;
+        self.end_task();
+        match result {
+            Ok(project) => self.set_status(format!(
+                "{} Ready: cd {} && cargo run",
+                self.icons.ok,
+                project.display()
+            )),
+            Err(e) => self.set_status(format!("{} {}", self.icons.error, e)),
+        }
     }
 
-    fn previous_page(&mut self) {
-        let jump = 10;
-        let i = match self.list_state.selected() {
-            Some(i) => {
-                if i < jump {
-                    0
-                } else {
-                    i - jump
-                }
+    /// Ask before `:reset` deletes saved state.
+    fn confirm_reset(&mut self, all: bool) {
+        self.set_status(if all {
+            "Delete favorites, session, snapshots and the data cache, and restore settings? (y/n)"
+        } else {
+            "Delete favorites and session state, and restore settings? (y/n)"
+        });
+        self.pending_action = Some(PendingAction::Reset { all });
+        self.mode = Mode::Confirm;
+    }
+
+    /// Delete the session and favorites files (plus the cache and snapshots when `all`), and
+    /// return runtime settings and the list to their startup state.
+    fn reset_state(&mut self, all: bool) {
+        let mut files = vec![session::get_session_file(), favorites::get_favorites_file()];
+        if all {
+            files.push(cache::get_cache_file());
+            files.push(cache::get_etag_file());
+        }
+
+        let mut removed = vec![];
+        let mut failed = vec![];
+        for path in files.into_iter().flatten() {
+            match fs::remove_file(&path) {
+                Ok(()) => removed.push(path.display().to_string()),
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+                Err(e) => failed.push(format!("{} ({})", path.display(), e)),
             }
-            None => 0,
+        }
+        if all {
+            match cache::clear_snapshots() {
+                Ok(0) => {}
+                Ok(n) => removed.push(format!("{} snapshots", n)),
+                Err(e) => failed.push(format!("snapshots ({:#})", e)),
+            }
+        }
+
+        self.favorites = Favorites::default();
+        self.favorites_return = None;
+        self.recently_viewed.clear();
+        self.visited.clear();
+        self.viewing = None;
+        self.compact_list = self.ui_config.compact_list;
+        self.gutter = self.ui_config.gutter;
+        self.count_as_percent = false;
+        self.mute_core = self.ui_config.mute_core;
+        self.trend_markers = self.ui_config.trend_markers;
+        self.select_top_result = self.ui_config.select_top_result;
+        self.scroll_margin = self.ui_config.scroll_margin;
+        self.description_lines = self.ui_config.description_lines;
+        self.full_description = false;
+        self.poll_interval = Duration::from_millis(self.ui_config.poll_interval_ms.max(1));
+        self.list_history.clear();
+        self.last_search.clear();
+        self.reset_list();
+        self.reset_selection();
+
+        let mut status = if removed.is_empty() {
+            "Reset: no saved state to remove; settings restored".to_string()
+        } else {
+            format!(
+                "{} Reset: removed {}; settings restored",
+                self.icons.ok,
+                summarize(&removed)
+            )
         };
-        self.list_state.select(Some(i));
+        if !failed.is_empty() {
+            status.push_str(&format!(
+                "; {} could not remove {}",
+                self.icons.error,
+                summarize(&failed)
+            ));
+        }
+        self.set_status(status);
     }
 
-    //     fn execute_command(&mut self) {
-    //         let cmd = self.command_input.trim();
-    //
-    //         if cmd.is_empty() {
-    //             self.mode = Mode::Normal;
-    //             return;
-    //         }
-    //
-    //         // Parse command
-    //         let parts: Vec<&str> = cmd.split_whitespace().collect();
-    //         let command = parts[0];
-    //
-    //         match command {
-    //             "q" | "quit" => {
-    //                 // Will be handled in main loop
-    //             }
-    //             "core" => {
-    //                 self.filtered_crates = self.all_crates
-    //                     .iter()
-    //                     .filter(|c| c.is_core_library)
-    //                     .cloned()
-    //                     .collect();
-    //                 self.list_state.select(Some(0));
-    //                 self.status_message = format!("Showing {} core libraries", self.filtered_crates.len());
-    //             }
-    //             "all" => {
-    //                 self.filtered_crates = self.all_crates.clone();
-    //                 self.list_state.select(Some(0));
-    //                 self.status_message = format!("Showing all {} crates", self.filtered_crates.len());
-    //             }
-    //             "top" => {
-    //                 let limit: usize = parts.get(1)
-    //                     .and_then(|s| s.parse().ok())
-    //                     .unwrap_or(10);
-    //
-    //                 let mut sorted = self.all_crates.clone();
-    //                 sorted.sort_by(|a, b| b.downloads.cmp(&a.downloads));
-    //                 self.filtered_crates = sorted.into_iter().take(limit).collect();
-    //                 self.list_state.select(Some(0));
-    //                 self.status_message = format!("Showing top {} by downloads", limit);
-    //             }
-    //             "recent" => {
-    //                 let limit: usize = parts.get(1)
-    //                     .and_then(|s| s.parse().ok())
-    //                     .unwrap_or(10);
-    //
-    //                 let mut sorted = self.all_crates.clone();
-    //                 sorted.sort_by(|a, b| b.recent_downloads.cmp(&a.recent_downloads));
-    //                 self.filtered_crates = sorted.into_iter().take(limit).collect();
-    //                 self.list_state.select(Some(0));
-    //                 self.status_message = format!("Showing top {} by weekly downloads", limit);
-    //             }
-    //             "new" => {
-    //                 let limit: usize = parts.get(1)
-    //                     .and_then(|s| s.parse().ok())
-    //                     .unwrap_or(10);
-    //
-    //                 let mut sorted = self.all_crates.clone();
-    //                 sorted.sort_by(|a, b| b.created_at.cmp(&a.created_at));
-    //                 self.filtered_crates = sorted.into_iter().take(limit).collect();
-    //                 self.list_state.select(Some(0));
-    //                 self.status_message = format!("Showing {} newest crates", limit);
-    //             }
-    //             "search" | "/" => {
-    //                 if parts.len() > 1 {
-    //                     let query = parts[1..].join(" ").to_lowercase();
-    //                     self.last_search = query.clone();
-    //                     self.filtered_crates = self.all_crates
-    //                         .iter()
-    //                         .filter(|c| {
-    //                             c.name.to_lowercase().contains(&query)
-    //                                 || c.description.to_lowercase().contains(&query)
-    //                         })
-    //                         .cloned()
-    //                         .collect();
-    //                     self.list_state.select(Some(0));
-    //                     self.status_message = format!(
-    //                         "Found {} crates matching '{}'",
-    //                         self.filtered_crates.len(),
-    //                         self.last_search
-    //                     );
-    //                 } else {
-    //                     self.status_message = "Usage: :search <query> or /<query>".to_string();
-    //                 }
-    //             }
-    //             "help" | "?" => {
-    //                 self.view = if self.view == View::Help { View::List } else { View::Help };
-    //                 self.status_message = if self.view == View::Help {
-    //                     "Showing help - Press ? or TAB to go back".to_string()
-    //                 } else {
-    //                     "Help hidden".to_string()
-    //                 };
-    //             }
-    //             "try" => {
-    //                 if let Some(crate_pkg) = self.selected_crate().cloned() {
-    //                     self.try_crate = Some(crate_pkg.name.clone());
-    //                     self.mode = Mode::Try;
-    //                     self.status_message = format!(
-    //                         "Try '{}' in /tmp/ratcrate-try? Press 'y' to confirm, 'n' to cancel",
-    //                         crate_pkg.name
-    //                     );
-    //                 } else {
-    //                     self.status_message = "No crate selected".to_string();
-    //                 }
-    //             }
-    //             _ => {
-    //                 // Try as search query
-    //                 let query = cmd.to_lowercase();
-    //                 self.last_search = query.clone();
-    //                 self.filtered_crates = self.all_crates
-    //                     .iter()
-    //                     .filter(|c| {
-    //                         c.name.to_lowercase().contains(&query)
-    //                             || c.description.to_lowercase().contains(&query)
-    //                     })
-    //                     .cloned()
-    //                     .collect();
-    //                 self.list_state.select(Some(0));
-    //                 self.status_message = format!(
-    //                     "Found {} crates matching '{}'",
-    //                     self.filtered_crates.len(),
-    //                     query
-    //                 );
-    //             }
-    //         }
-    //
-    //         self.command_input.clear();
-    //         self.mode = Mode::Normal;
-    //     }
-    //
+    /// Drop the pending action after the user pressed 'n' or Esc.
+    fn cancel_pending(&mut self) {
+        self.mode = Mode::Normal;
+        self.pending_action = None;
+        self.set_status("Cancelled");
+    }
 
-    fn execute_command(&mut self) {
-        let cmd = self.command_input.trim();
+    /// Select the first crate, or nothing if the list is empty.
+    fn reset_selection(&mut self) {
+        let first = if self.filtered_crates.is_empty() {
+            None
+        } else {
+            Some(0)
+        };
+        self.list_state.select(first);
+    }
 
-        if cmd.is_empty() {
-            self.mode = Mode::Normal;
+    /// Remember `ops` for `:prev`, dropping the oldest entry once `LIST_HISTORY_DEPTH` are
+    /// kept.
+    fn push_list_history(&mut self, ops: Vec<ListOp>) {
+        if self.list_history.len() == LIST_HISTORY_DEPTH {
+            self.list_history.remove(0);
+        }
+        self.list_history.push(ops);
+    }
+
+    /// Apply a list step, remembering the previous list so `:prev` can return to it.
+    fn apply_list_op(&mut self, op: ListOp) {
+        self.push_list_history(self.list_ops.clone());
+
+        if op.resets() {
+            self.list_ops.clear();
+        }
+        let current = std::mem::take(&mut self.filtered_crates);
+        self.set_filtered(op.apply(&self.all_crates, current));
+        // An explicit sort or reversal means the first row is the one wanted
+        let pick_top = self.select_top_result && !matches!(op, ListOp::Sort(_) | ListOp::Reverse);
+        self.list_ops.push(op);
+        self.reset_selection();
+        if pick_top {
+            self.select_most_downloaded();
+        }
+    }
+
+    /// Select the most downloaded crate in the list; the earliest wins a tie.
+    fn select_most_downloaded(&mut self) {
+        let top = self
+            .filtered_crates
+            .iter()
+            .enumerate()
+            .rev()
+            .max_by_key(|(_, c)| c.downloads)
+            .map(|(i, _)| i);
+        if top.is_some() {
+            self.list_state.select(top);
+        }
+    }
+
+    /// Step back to the list shown before the last filter/search, like a browser back button.
+    fn previous_list(&mut self) {
+        let Some(ops) = self.list_history.pop() else {
+            self.set_status("No earlier list to go back to");
             return;
+        };
+
+        self.set_filtered(filter::replay(&ops, &self.all_crates));
+        self.list_ops = ops;
+        self.reset_selection();
+        self.set_status(format!(
+            "Back to {} ({} crates)",
+            self.describe_list(),
+            self.filtered_crates.len()
+        ));
+    }
+
+    /// Whether to give this crate the core-library star and yellow styling.
+    fn highlights_core(&self, c: &CratePackage) -> bool {
+        c.is_core_library && !self.mute_core
+    }
+
+    /// Sort the list by name (if it isn't already) and wait for a letter to jump to.
+    fn start_letter_jump(&mut self) {
+        let by_name = SortOrder::by(SortStep::BY_NAME);
+        self.jump_sorted = self.active_sort() != Some(by_name);
+        if self.jump_sorted {
+            self.apply_list_op(ListOp::Sort(by_name));
+            self.set_status(
+                "Sorted by name; type a letter to jump (Esc restores the previous order)",
+            );
+        } else {
+            self.set_status("Type a letter to jump");
         }
+        self.mode = Mode::Jump;
+    }
 
-        // Parse command
-        let parts: Vec<&str> = cmd.split_whitespace().collect();
-        let command = parts[0];
+    /// Select the first crate whose name starts at or after `letter`. When the selection
+    /// already starts with `letter`, move on to the next such crate, wrapping around.
+    fn jump_to_letter(&mut self, letter: char) {
+        self.mode = Mode::Normal;
+        let letter = letter.to_ascii_lowercase();
+        let first_letter = |c: &CratePackage| c.name.chars().next().map(|f| f.to_ascii_lowercase());
+        let starts_with = |c: &CratePackage| first_letter(c) == Some(letter);
 
-        match command {
-            "q" | "quit" => {
-                // Will be handled in main loop
-            }
-            "core" => {
-                self.filtered_crates = self
-                    .all_crates
+        let next_same = self
+            .list_state
+            .selected()
+            .filter(|&i| self.filtered_crates.get(i).is_some_and(starts_with))
+            .and_then(|i| {
+                self.filtered_crates[i + 1..]
                     .iter()
-                    .filter(|c| c.is_core_library)
-                    .cloned()
-                    .collect();
-                self.list_state.select(Some(0));
-                self.status_message =
-                    format!("Showing {} core libraries", self.filtered_crates.len());
-            }
-            "all" => {
-                self.filtered_crates = self.all_crates.clone();
-                self.list_state.select(Some(0));
-                self.status_message = format!("Showing all {} crates", self.filtered_crates.len());
-            }
-            "top" => {
-                let limit: usize = parts.get(1).and_then(|s| s.parse().ok()).unwrap_or(10);
+                    .position(starts_with)
+                    .map(|offset| i + 1 + offset)
+            });
+        let position = next_same.or_else(|| {
+            self.filtered_crates
+                .iter()
+                .position(|c| first_letter(c).is_some_and(|first| first >= letter))
+        });
 
-                let mut sorted = self.all_crates.clone();
-                sorted.sort_by(|a, b| b.downloads.cmp(&a.downloads));
-                self.filtered_crates = sorted.into_iter().take(limit).collect();
-                self.list_state.select(Some(0));
-                self.status_message = format!("Showing top {} by downloads", limit);
+        match position {
+            Some(i) => {
+                self.list_state.select(Some(i));
+                self.set_status(format!("Jumped to '{}'", letter));
             }
-            "recent" => {
-                let limit: usize = parts.get(1).and_then(|s| s.parse().ok()).unwrap_or(10);
+            None => self.set_status(format!("No crates at or after '{}'", letter)),
+        }
+    }
 
-                let mut sorted = self.all_crates.clone();
-                sorted.sort_by(|a, b| b.recent_downloads.cmp(&a.recent_downloads));
-                self.filtered_crates = sorted.into_iter().take(limit).collect();
-                self.list_state.select(Some(0));
-                self.status_message = format!("Showing top {} by weekly downloads", limit);
-            }
-            "new" => {
-                let limit: usize = parts.get(1).and_then(|s| s.parse().ok()).unwrap_or(10);
+    /// Select the next crate after the selection that matches the last search the way
+    /// `:search` does (fuzzy name or description), wrapping around.
+    fn jump_to_search_match(&mut self) {
+        if self.last_search.is_empty() {
+            self.set_status("No search to repeat: search with / first");
+            return;
+        }
+        let len = self.filtered_crates.len();
+        let start = self.list_state.selected().map_or(0, |i| i + 1);
+        let found = (0..len)
+            .map(|k| (start + k) % len)
+            .find(|&i| filter::fuzzy_score(&self.filtered_crates[i], &self.last_search).is_some());
 
-                let mut sorted = self.all_crates.clone();
-                sorted.sort_by(|a, b| b.created_at.cmp(&a.created_at));
-                self.filtered_crates = sorted.into_iter().take(limit).collect();
-                self.list_state.select(Some(0));
-                self.status_message = format!("Showing {} newest crates", limit);
-            }
-            "search" | "/" => {
-                if parts.len() > 1 {
-                    let query = parts[1..].join(" ").to_lowercase();
-                    self.last_search = query.clone();
-                    self.filtered_crates = self
-                        .all_crates
-                        .iter()
-                        .filter(|c| {
-                            c.name.to_lowercase().contains(&query)
-                                || c.description.to_lowercase().contains(&query)
-                        })
-                        .cloned()
-                        .collect();
-                    self.list_state.select(Some(0));
-                    self.status_message = format!(
-                        "Found {} crates matching '{}'",
-                        self.filtered_crates.len(),
-                        self.last_search
-                    );
-                } else {
-                    self.status_message = "Usage: :search <query> or /<query>".to_string();
-                }
-            }
-            "help" | "?" => {
-                self.view = if self.view == View::Help {
-                    View::List
-                } else {
-                    View::Help
-                };
-                self.status_message = if self.view == View::Help {
-                    "Showing help - Press ? or TAB to go back".to_string()
-                } else {
-                    "Help hidden".to_string()
-                };
-            }
-            // "try" => {
-            //     if let Some(crate_pkg) = self.selected_crate().cloned() {
-            //         self.try_crate = Some(crate_pkg.name.clone());
-            //         self.mode = Mode::Try;
-            //         self.status_message = format!(
-            //             "Try '{}' in /tmp/ratcrate-try? Press 'y' to confirm, 'n' to cancel",
-            //             crate_pkg.name
-            //         );
-            //     } else {
-            //         self.status_message = "No crate selected".to_string();
-            //     }
-            // }
-            _ => {
-                // Try as search query
-                let query = cmd.to_lowercase();
-                self.last_search = query.clone();
-                self.filtered_crates = self
-                    .all_crates
-                    .iter()
-                    .filter(|c| {
-                        c.name.to_lowercase().contains(&query)
-                            || c.description.to_lowercase().contains(&query)
-                    })
-                    .cloned()
-                    .collect();
-                self.list_state.select(Some(0));
-                self.status_message = format!(
-                    "Found {} crates matching '{}'",
-                    self.filtered_crates.len(),
-                    query
-                );
+        match found {
+            Some(i) => {
+                self.list_state.select(Some(i));
+                let wrapped = if i < start { " (wrapped)" } else { "" };
+                self.set_status(format!(
+                    "Next match for '{}': {}{}",
+                    self.last_search, self.filtered_crates[i].name, wrapped
+                ));
             }
+            None => self.set_status(format!(
+                "No crate in the list matches '{}'",
+                self.last_search
+            )),
         }
+    }
 
-        // Clear typed command, but DO NOT forcibly exit Try mode if we just entered it.
-        self.command_input.clear();
-        // if self.mode != Mode::Try {
+    /// Leave Jump mode, undoing the name sort if entering it applied one.
+    fn cancel_letter_jump(&mut self) {
         self.mode = Mode::Normal;
-        // }
+        if self.jump_sorted {
+            self.previous_list();
+        } else {
+            self.set_status("Jump cancelled");
+        }
     }
-}
 
-// ============================================================================
-// UI Rendering
-// ============================================================================
+    /// Show the whole description in the detail pane, or cut it to `ui.description_lines`.
+    fn set_full_description(&mut self, full: bool) {
+        self.full_description = full;
+        self.set_status(if full {
+            "Showing full descriptions".to_string()
+        } else if self.description_lines == 0 {
+            "Descriptions are not limited (ui.description_lines = 0)".to_string()
+        } else {
+            format!(
+                "Showing the first {} description lines",
+                self.description_lines
+            )
+        });
+    }
 
-fn ui(f: &mut Frame, app: &mut App) {
-    let chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Min(0),    // Main content
-            Constraint::Length(3), // Command/status bar
-        ])
-        .split(f.area());
+    /// Show or hide the category browser in the right pane.
+    fn toggle_categories(&mut self) {
+        self.view = if self.view == View::Categories {
+            View::List
+        } else {
+            View::Categories
+        };
+        self.set_status(if self.view == View::Categories {
+            "Categories - j/k to move, Enter to filter, C to go back"
+        } else {
+            "Categories hidden"
+        });
+    }
 
-    let main_chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Percentage(35), // List
-            Constraint::Percentage(65), // Detail
-        ])
-        .split(chunks[0]);
+    /// Keys that act on the category browser while it is shown. Returns whether the key was used.
+    fn handle_category_key(&mut self, code: KeyCode) -> bool {
+        let len = self.categories.len();
+        let selected = self.category_state.selected().unwrap_or(0);
+        match code {
+            KeyCode::Char('j') | KeyCode::Down if len > 0 => {
+                self.category_state.select(Some((selected + 1) % len));
+            }
+            KeyCode::Char('k') | KeyCode::Up if len > 0 => {
+                self.category_state
+                    .select(Some(selected.checked_sub(1).unwrap_or(len - 1)));
+            }
+            KeyCode::Char('g') if len > 0 => self.category_state.select(Some(0)),
+            KeyCode::Char('G') if len > 0 => self.category_state.select(Some(len - 1)),
+            KeyCode::Enter => self.filter_selected_category(),
+            KeyCode::Esc => self.toggle_categories(),
+            _ => return false,
+        }
+        true
+    }
 
-    // Render list
-    render_list(f, app, main_chunks[0]);
+    /// Filter the list to the category selected in the browser and return to the list view.
+    fn filter_selected_category(&mut self) {
+        let Some((category, _)) = self
+            .category_state
+            .selected()
+            .and_then(|i| self.categories.get(i))
+            .cloned()
+        else {
+            return;
+        };
 
-    // Render detail/help/stats based on view
-    match app.view {
-        View::List => render_detail(f, app, main_chunks[1]),
-        View::Help => render_help(f, main_chunks[1]),
-        View::Stats => render_stats(f, app, main_chunks[1]),
+        self.apply_list_op(ListOp::Category(category.clone()));
+        self.view = View::List;
+        self.set_status(format!(
+            "Showing {} crates in {}",
+            self.filtered_crates.len(),
+            self.category_label(category.as_deref())
+        ));
     }
 
-    // Render command/status bar
-    render_command_bar(f, app, chunks[1]);
-}
+    /// Filter the list to a category typed by the user, in any casing.
+    fn filter_category(&mut self, name: &str) {
+        let category = filter::normalize_category(name);
+        if !self.category_counts.contains_key(&category) {
+            self.set_status(format!("No category '{}'", name.trim()));
+            return;
+        }
+        self.apply_list_op(ListOp::Category(Some(category.clone())));
+        self.set_status(format!(
+            "Showing {} crates in {}",
+            self.filtered_crates.len(),
+            self.category_label(Some(&category))
+        ));
+    }
 
-fn render_list(f: &mut Frame, app: &mut App, area: Rect) {
-    let items: Vec<ListItem> = app
-        .filtered_crates
-        .iter()
-        .enumerate()
-        .map(|(_idx, crate_pkg)| {
-            let icon = if crate_pkg.is_core_library {
-                "⭐"
-            } else {
-                "📦"
-            };
+    /// Name every category in the dataset, A→Z, in the status bar.
+    fn list_categories(&mut self) {
+        if self.category_counts.is_empty() {
+            self.set_status("No crate has a category");
+            return;
+        }
+        let mut names: Vec<&str> = self
+            .category_counts
+            .keys()
+            .map(|category| self.category_label(Some(category)))
+            .collect();
+        names.sort_by_key(|name| name.to_lowercase());
+        self.set_status(format!(
+            "{} categories (:category <name>): {}",
+            names.len(),
+            names.join(", ")
+        ));
+    }
 
-            // Create a colorful list item
-            let content = vec![
-                Line::from(vec![
-                    Span::styled(
-                        format!("{} ", icon),
-                        if crate_pkg.is_core_library {
-                            Style::default().fg(Color::Yellow)
-                        } else {
-                            Style::default().fg(Color::Cyan)
-                        },
-                    ),
-                    Span::styled(
-                        &crate_pkg.name,
-                        if crate_pkg.is_core_library {
-                            Style::default()
-                                .fg(Color::Yellow)
-                                .add_modifier(Modifier::BOLD)
-                        } else {
-                            Style::default()
-                                .fg(Color::Cyan)
-                                .add_modifier(Modifier::BOLD)
-                        },
-                    ),
-                ]),
-                Line::from(vec![
-                    Span::styled("  ", Style::default()),
-                    Span::styled("↓ ", Style::default().fg(Color::Green)),
-                    Span::styled(
-                        format_number(crate_pkg.downloads),
-                        Style::default().fg(Color::Green),
-                    ),
-                    Span::styled(" 📈 ", Style::default().fg(Color::Blue)),
-                    Span::styled(
-                        format_number(crate_pkg.recent_downloads),
-                        Style::default().fg(Color::Blue),
-                    ),
-                ]),
-            ];
-
-            ListItem::new(content)
-        })
-        .collect();
-
-    let list = List::new(items)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Cyan))
-                .title(vec![
-                    Span::styled(
-                        " 📦 Crates ",
-                        Style::default()
-                            .fg(Color::Cyan)
-                            .add_modifier(Modifier::BOLD),
-                    ),
-                    Span::styled(
-                        format!("({}/{}) ", app.filtered_crates.len(), app.all_crates.len()),
-                        Style::default().fg(Color::DarkGray),
-                    ),
-                ])
-                .style(Style::default()),
-        )
-        .highlight_style(
-            Style::default()
-                .bg(Color::Rgb(60, 60, 80))
-                .add_modifier(Modifier::BOLD),
-        )
-        .highlight_symbol("▶ ");
+    /// Display spelling of a normalized category; `None` is the uncategorized bucket.
+    fn category_label<'a>(&'a self, category: Option<&'a str>) -> &'a str {
+        match category {
+            Some(category) => self
+                .category_labels
+                .get(category)
+                .map_or(category, String::as_str),
+            None => UNCATEGORIZED,
+        }
+    }
 
-    f.render_stateful_widget(list, area, &mut app.list_state);
-}
+    /// Select the next (or previous) favorited crate in the current list, wrapping around.
+    fn jump_to_favorite(&mut self, forward: bool) {
+        let len = self.filtered_crates.len();
+        let current = self.list_state.selected().unwrap_or(0);
+        let found = (1..=len)
+            .map(|step| {
+                if forward {
+                    (current + step) % len
+                } else {
+                    (current + len - step % len) % len
+                }
+            })
+            .find(|&i| self.favorites.contains(&self.filtered_crates[i].id));
+        match found {
+            Some(i) => {
+                let wrapped = if forward { i <= current } else { i >= current };
+                self.list_state.select(Some(i));
+                if wrapped {
+                    self.set_status("Wrapped around the list");
+                }
+            }
+            None => self.set_status("No favorites in this list"),
+        }
+    }
 
-fn render_detail(f: &mut Frame, app: &App, area: Rect) {
-    let detail = if let Some(crate_pkg) = app.selected_crate() {
-        let mut lines = vec![];
+    /// Star or unstar the selected crate and save the favorites file.
+    fn toggle_favorite(&mut self) {
+        let Some(crate_pkg) = self.selected_crate().cloned() else {
+            self.set_status("No crate selected");
+            return;
+        };
 
-        // Title with colorful icon
-        let icon = if crate_pkg.is_core_library {
-            "⭐"
+        let added = self.favorites.toggle(&crate_pkg);
+        if let Err(e) = self.favorites.save() {
+            self.set_status(format!("{} {:#}", self.icons.error, e));
+            return;
+        }
+        let mut status = if added {
+            format!(
+                "{} Added {} to favorites",
+                self.icons.favorite, crate_pkg.name
+            )
         } else {
-            "📦"
+            format!("Removed {} from favorites", crate_pkg.name)
         };
-        lines.push(Line::from(vec![
-            Span::styled(
-                format!("{} {} ", icon, crate_pkg.name),
-                Style::default()
-                    .fg(if crate_pkg.is_core_library {
-                        Color::Yellow
-                    } else {
-                        Color::Cyan
-                    })
-                    .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
-            ),
-            Span::styled(
-                format!("v{}", crate_pkg.version),
-                Style::default().fg(Color::Magenta),
-            ),
-        ]));
-
-        if crate_pkg.is_core_library {
-            lines.push(Line::from(Span::styled(
-                "⭐ CORE LIBRARY ⭐",
-                Style::default()
-                    .fg(Color::Yellow)
-                    .add_modifier(Modifier::BOLD),
-            )));
+        if !self.persistent {
+            status.push_str(" (this session only)");
         }
+        self.set_status(status);
+    }
 
-        lines.push(Line::from(""));
+    /// Favorites whose id is not in the current dataset
+    fn orphaned_favorites(&self) -> Vec<&Favorite> {
+        self.favorites
+            .entries
+            .iter()
+            .filter(|f| !self.id_index.contains_key(&f.id))
+            .collect()
+    }
 
-        // Description with nice formatting
-        lines.push(Line::from(Span::styled(
-            "📝 Description:",
-            Style::default()
-                .fg(Color::Green)
-                .add_modifier(Modifier::BOLD),
-        )));
+    fn favorites_only(&self) -> bool {
+        matches!(self.list_ops.first(), Some(ListOp::Favorites(_)))
+    }
 
-        // Simple word wrapping for description
-        let words: Vec<&str> = crate_pkg.description.split_whitespace().collect();
-        let mut current_line = String::new();
-
-        for word in words {
-            if current_line.len() + word.len() + 1 > 60 {
-                lines.push(Line::from(Span::styled(
-                    format!("  {}", current_line),
-                    Style::default().fg(Color::White),
-                )));
-                current_line = word.to_string();
-            } else {
-                if !current_line.is_empty() {
-                    current_line.push(' ');
-                }
-                current_line.push_str(word);
+    /// Show only favorited crates.
+    fn show_favorites(&mut self) {
+        self.apply_list_op(ListOp::Favorites(self.favorites.ids()));
+        let mut status = format!(
+            "{} Showing {} favorites",
+            self.icons.favorite,
+            self.filtered_crates.len()
+        );
+        let orphans = self.orphaned_favorites().len();
+        if orphans > 0 {
+            status.push_str(&format!(" ({} not in dataset)", orphans));
+        }
+        self.set_status(status);
+    }
+
+    /// Flip between the current list and favorites only, keeping the selected crate if it is
+    /// in both. Toggling back restores the list that was shown before.
+    fn toggle_favorites_only(&mut self) {
+        let selected_id = self.selected_crate().map(|c| c.id.clone());
+
+        match self.favorites_return.take() {
+            Some(ops) if self.favorites_only() => {
+                self.push_list_history(self.list_ops.clone());
+                self.set_filtered(filter::replay(&ops, &self.all_crates));
+                self.list_ops = ops;
+                self.reset_selection();
+                self.set_status(format!(
+                    "Back to {} ({} crates)",
+                    self.describe_list(),
+                    self.filtered_crates.len()
+                ));
+            }
+            _ => {
+                self.favorites_return = Some(self.list_ops.clone());
+                self.show_favorites();
             }
         }
-        if !current_line.is_empty() {
-            lines.push(Line::from(Span::styled(
-                format!("  {}", current_line),
-                Style::default().fg(Color::White),
-            )));
+
+        if let Some(id) = selected_id {
+            self.select_id(&id);
         }
-        lines.push(Line::from(""));
+    }
 
-        // Statistics with icons and colors
-        lines.push(Line::from(Span::styled(
-            "📊 Statistics:",
-            Style::default()
-                .fg(Color::Green)
-                .add_modifier(Modifier::BOLD),
-        )));
-        lines.push(Line::from(vec![
-            Span::raw("  "),
-            Span::styled("↓ Downloads:       ", Style::default().fg(Color::DarkGray)),
-            Span::styled(
-                format_number(crate_pkg.downloads),
-                Style::default()
-                    .fg(Color::Green)
-                    .add_modifier(Modifier::BOLD),
-            ),
-        ]));
-        lines.push(Line::from(vec![
-            Span::raw("  "),
-            Span::styled("📈 Weekly:          ", Style::default().fg(Color::DarkGray)),
-            Span::styled(
-                format_number(crate_pkg.recent_downloads),
-                Style::default()
-                    .fg(Color::Blue)
-                    .add_modifier(Modifier::BOLD),
-            ),
-        ]));
-        lines.push(Line::from(""));
+    /// Recount matches for a `search <query>` being typed, without touching the list.
+    fn update_search_preview(&mut self) {
+        let (query, exact) = match self.command_input.strip_prefix("search! ") {
+            Some(query) => (Some(query), true),
+            None => (self.command_input.strip_prefix("search "), false),
+        };
+        self.search_preview = query
+            .map(|query| query.trim().to_lowercase())
+            .filter(|query| !query.is_empty())
+            .map(|query| {
+                self.all_crates
+                    .iter()
+                    .filter(|c| {
+                        if exact {
+                            filter::matches_search(c, &query)
+                        } else {
+                            filter::fuzzy_score(c, &query).is_some()
+                        }
+                    })
+                    .count()
+            });
+    }
 
-        // Install command with colorful box
-        lines.push(Line::from(Span::styled(
-            "📦 Install:",
-            Style::default()
-                .fg(Color::Green)
-                .add_modifier(Modifier::BOLD),
-        )));
-        lines.push(Line::from(vec![
-            Span::raw("  "),
-            Span::styled(
-                format!("cargo add {}", crate_pkg.name),
-                Style::default()
-                    .fg(Color::Cyan)
-                    .add_modifier(Modifier::BOLD),
-            ),
-        ]));
-        lines.push(Line::from(""));
+    /// Remember an executed command for Up/Down recall, skipping blanks and repeats.
+    fn record_command(&mut self) {
+        let cmd = self.command_input.trim();
+        if !cmd.is_empty() && self.command_history.last().map(String::as_str) != Some(cmd) {
+            self.command_history.push(cmd.to_string());
+        }
+    }
 
-        // Try mode hint
-        lines.push(Line::from(vec![
-            Span::styled(
-                "💡 Tip: ",
-                Style::default()
-                    .fg(Color::Yellow)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::styled("Use ", Style::default().fg(Color::DarkGray)),
-            Span::styled(
-                ":try",
-                Style::default()
-                    .fg(Color::Cyan)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::styled(
-                " to test this crate in a temporary project!",
-                Style::default().fg(Color::DarkGray),
-            ),
-        ]));
-        lines.push(Line::from(""));
+    /// Up/Down in command mode: step through earlier commands like a shell. Stepping past
+    /// the newest entry leaves an empty prompt.
+    fn recall_command(&mut self, older: bool) {
+        let len = self.command_history.len();
+        self.history_cursor = if older {
+            self.history_cursor.saturating_sub(1)
+        } else {
+            (self.history_cursor + 1).min(len)
+        };
+        self.command_input = self
+            .command_history
+            .get(self.history_cursor)
+            .cloned()
+            .unwrap_or_default();
+        self.update_search_preview();
+    }
 
-        // Links with icons
-        if crate_pkg.repository.is_some()
-            || crate_pkg.documentation.is_some()
-            || crate_pkg.homepage.is_some()
-        {
-            lines.push(Line::from(Span::styled(
-                "🔗 Links:",
-                Style::default()
-                    .fg(Color::Green)
-                    .add_modifier(Modifier::BOLD),
-            )));
+    /// Tab in command mode: complete the command name, or the category after `category `.
+    /// A single match is filled in; several are cycled through on repeated Tab.
+    fn complete_command(&mut self) {
+        if let Some((prefix, candidates, i)) = &mut self.completion {
+            *i = (*i + 1) % candidates.len();
+            self.command_input = format!("{}{}", prefix, candidates[*i]);
+            return;
+        }
 
-            if let Some(repo) = &crate_pkg.repository {
-                lines.push(Line::from(vec![
-                    Span::raw("  "),
-                    Span::styled("📁 Repo:  ", Style::default().fg(Color::DarkGray)),
-                    Span::styled(repo, Style::default().fg(Color::Blue)),
-                ]));
+        let input = self.command_input.trim_start().to_string();
+        let (prefix, candidates) = match input.strip_prefix("category ") {
+            Some(partial) => {
+                let partial = partial.to_lowercase();
+                let mut names: Vec<String> = self
+                    .category_counts
+                    .keys()
+                    .map(|category| self.category_label(Some(category)).to_string())
+                    .filter(|name| name.to_lowercase().starts_with(&partial))
+                    .collect();
+                names.sort_by_key(|name| name.to_lowercase());
+                ("category ", names)
             }
-            if let Some(docs) = &crate_pkg.documentation {
-                lines.push(Line::from(vec![
-                    Span::raw("  "),
-                    Span::styled("📖 Docs:  ", Style::default().fg(Color::DarkGray)),
-                    Span::styled(docs, Style::default().fg(Color::Blue)),
-                ]));
+            None if !input.contains(' ') => {
+                let names = COMMANDS
+                    .iter()
+                    .filter(|name| name.starts_with(&input))
+                    .map(|name| name.to_string())
+                    .collect();
+                ("", names)
             }
-            if let Some(home) = &crate_pkg.homepage {
-                lines.push(Line::from(vec![
-                    Span::raw("  "),
-                    Span::styled("🏠 Home:  ", Style::default().fg(Color::DarkGray)),
-                    Span::styled(home, Style::default().fg(Color::Blue)),
-                ]));
+            None => return,
+        };
+
+        match candidates.len() {
+            0 => {}
+            1 => {
+                self.command_input = format!("{}{}", prefix, candidates[0]);
+                if prefix.is_empty() {
+                    self.command_input.push(' ');
+                }
+            }
+            _ => {
+                self.command_input = format!("{}{}", prefix, candidates[0]);
+                self.completion = Some((prefix, candidates, 0));
             }
-            lines.push(Line::from(""));
         }
+        self.update_search_preview();
+    }
 
-        // Categories with colorful tags
-        if let Some(categories) = &crate_pkg.categories {
-            if !categories.is_empty() {
-                lines.push(Line::from(Span::styled(
-                    "🏷️  Categories:",
-                    Style::default()
-                        .fg(Color::Green)
-                        .add_modifier(Modifier::BOLD),
-                )));
-
-                let cat_spans: Vec<Span> = categories
-                    .iter()
-                    .flat_map(|cat| {
-                        vec![
-                            Span::styled("  [", Style::default().fg(Color::DarkGray)),
-                            Span::styled(
-                                cat,
-                                Style::default()
-                                    .fg(Color::Magenta)
-                                    .add_modifier(Modifier::BOLD),
-                            ),
-                            Span::styled("]", Style::default().fg(Color::DarkGray)),
-                            Span::raw(" "),
-                        ]
-                    })
-                    .collect();
+    /// Start a `/` search: the list follows the query as it is typed.
+    fn start_live_search(&mut self) {
+        self.mode = Mode::Search;
+        self.command_input.clear();
+        self.search_origin = Some((self.list_ops.clone(), self.list_state.selected()));
+    }
 
-                lines.push(Line::from(cat_spans));
-            }
+    /// Re-filter the list for the query typed so far; an empty query shows the list from
+    /// before `/` again.
+    fn update_live_search(&mut self) {
+        let query = self.command_input.trim().to_lowercase();
+        if query.is_empty() {
+            self.restore_search_origin();
+            return;
         }
+        self.list_ops = vec![ListOp::Fuzzy(query)];
+        self.set_filtered(filter::replay(&self.list_ops, &self.all_crates));
+        self.reset_selection();
+    }
 
-        Text::from(lines)
-    } else {
-        Text::from(vec![
-            Line::from(""),
-            Line::from(Span::styled(
-                "No crate selected",
-                Style::default().fg(Color::DarkGray),
-            )),
-            Line::from(""),
-            Line::from(Span::styled(
-                "Use j/k or ↑/↓ to navigate",
-                Style::default().fg(Color::DarkGray),
-            )),
-        ])
-    };
+    /// Enter on a `/` search: keep the results, with the earlier list one `:prev` away.
+    fn commit_live_search(&mut self) {
+        self.mode = Mode::Normal;
+        let query = self.command_input.trim().to_lowercase();
+        self.command_input.clear();
+        let Some((ops, selected)) = self.search_origin.take() else {
+            return;
+        };
+        // Nothing typed: leave quietly and keep the current list
+        if query.is_empty() {
+            self.list_state.select(selected);
+            return;
+        }
 
-    let paragraph = Paragraph::new(detail)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Cyan))
-                .title(Span::styled(
-                    " 📋 Detail ",
-                    Style::default()
-                        .fg(Color::Cyan)
-                        .add_modifier(Modifier::BOLD),
-                ))
-                .style(Style::default()),
-        )
-        .wrap(Wrap { trim: false });
+        self.push_list_history(ops);
+        self.last_search = query;
+        if self.select_top_result {
+            self.select_most_downloaded();
+        }
+        self.set_status(format!(
+            "Found {} crates matching '{}'",
+            self.filtered_crates.len(),
+            self.last_search
+        ));
+    }
 
-    f.render_widget(paragraph, area);
-}
+    /// Esc on a `/` search: put back the list and selection from before it.
+    fn cancel_live_search(&mut self) {
+        self.mode = Mode::Normal;
+        self.command_input.clear();
+        self.restore_search_origin();
+        if let Some((_, selected)) = self.search_origin.take() {
+            self.list_state.select(selected);
+        }
+    }
 
-fn render_help(f: &mut Frame, area: Rect) {
-    let help_text = vec![
-        Line::from(""),
-        Line::from(Span::styled(
-            "╔═══════════════════════════════════════════════════════╗",
-            Style::default()
-                .fg(Color::Cyan)
-                .add_modifier(Modifier::BOLD),
-        )),
-        Line::from(Span::styled(
-            "║                                                       ║",
-            Style::default()
-                .fg(Color::Cyan)
-                .add_modifier(Modifier::BOLD),
-        )),
-        Line::from(vec![
-            Span::styled(
-                "║   ",
-                Style::default()
-                    .fg(Color::Cyan)
-                    .add_modifier(Modifier::BOLD),
+    fn restore_search_origin(&mut self) {
+        let Some((ops, _)) = &self.search_origin else {
+            return;
+        };
+        if self.list_ops != *ops {
+            self.list_ops = ops.clone();
+            self.set_filtered(filter::replay(&self.list_ops, &self.all_crates));
+            self.reset_selection();
+        }
+    }
+
+    /// The steps behind the current list, e.g. "core > search 'tui'"
+    fn describe_list(&self) -> String {
+        if self.list_ops.is_empty() {
+            "all".to_string()
+        } else {
+            self.list_ops
+                .iter()
+                .map(ListOp::label)
+                .collect::<Vec<_>>()
+                .join(" > ")
+        }
+    }
+
+    /// Drop the `n`th (1-based) step of the current list, like removing a filter chip, and
+    /// rebuild the list from the steps that remain.
+    fn remove_list_op(&mut self, n: usize) {
+        let Some(i) = n.checked_sub(1).filter(|&i| i < self.list_ops.len()) else {
+            self.set_status(format!("No filter #{}", n));
+            return;
+        };
+        let selected_id = self.selected_crate().map(|c| c.id.clone());
+
+        self.push_list_history(self.list_ops.clone());
+        let removed = self.list_ops.remove(i);
+        self.set_filtered(filter::replay(&self.list_ops, &self.all_crates));
+        if !selected_id.is_some_and(|id| self.select_id(&id)) {
+            self.reset_selection();
+        }
+        self.set_status(format!(
+            "Removed {}: showing {} ({} crates)",
+            chip_label(&removed),
+            self.describe_list(),
+            self.filtered_crates.len()
+        ));
+    }
+
+    /// Flip the order of the current list and select its new first row. Reversing twice in
+    /// a row drops the first reversal instead of stacking another.
+    fn reverse_list(&mut self) {
+        if self.list_ops.last() == Some(&ListOp::Reverse) {
+            self.push_list_history(self.list_ops.clone());
+            self.list_ops.pop();
+            self.filtered_crates.reverse();
+            self.reset_selection();
+        } else {
+            self.apply_list_op(ListOp::Reverse);
+        }
+        self.set_status(format!(
+            "Reversed list: showing {} ({} crates)",
+            self.describe_list(),
+            self.filtered_crates.len()
+        ));
+    }
+
+    /// Replace the list with every crate that is *not* currently shown (matched by id).
+    fn invert_filter(&mut self) {
+        let shown_before = self.filtered_crates.len();
+        self.apply_list_op(ListOp::Invert);
+        self.set_status(format!(
+            "Inverted filter: showing {} crates that were hidden ({} were shown before)",
+            self.filtered_crates.len(),
+            shown_before
+        ));
+    }
+
+    /// Narrow the current list to crates whose total downloads fall on one side of `arg`.
+    fn filter_downloads(&mut self, arg: Option<&str>, minimum: bool) {
+        let usage = if minimum {
+            "Usage: :mindl <N> (e.g. 100000, 50K, 1.5M)"
+        } else {
+            "Usage: :maxdl <N> (e.g. 100000, 50K, 1.5M)"
+        };
+        let Some(threshold) = arg.and_then(parse_number) else {
+            self.set_status(usage);
+            return;
+        };
+
+        self.apply_list_op(if minimum {
+            ListOp::MinDownloads(threshold)
+        } else {
+            ListOp::MaxDownloads(threshold)
+        });
+        self.set_status(format!(
+            "Showing {} crates with {} {} downloads",
+            self.filtered_crates.len(),
+            if minimum { "at least" } else { "at most" },
+            format_number(threshold)
+        ));
+    }
+
+    /// Narrow the list to crates whose ratatui requirement satisfies `req`, e.g. `^0.26`.
+    fn filter_compat(&mut self, req: &str) {
+        let req = match semver::VersionReq::parse(req) {
+            Ok(req) => req,
+            Err(e) => {
+                self.set_status(format!(
+                    "Invalid version requirement '{}' ({}); e.g. :compat ^0.26",
+                    req, e
+                ));
+                return;
+            }
+        };
+        let skipped = self
+            .filtered_crates
+            .iter()
+            .filter(|c| filter::ratatui_version(c).is_none())
+            .count();
+
+        self.apply_list_op(ListOp::Compat(req.clone()));
+        let mut status = format!(
+            "Showing {} crates compatible with ratatui {}",
+            self.filtered_crates.len(),
+            req
+        );
+        if skipped > 0 {
+            status.push_str(&format!("; {} skipped (no version info)", skipped));
+        }
+        self.set_status(status);
+    }
+
+    /// List crates added to the dataset since an earlier version, using its cached snapshot.
+    fn show_new_since(&mut self, version: &str) {
+        let version = version.trim_start_matches('v');
+        let known = match cache::load_snapshot(version) {
+            Ok(known) => known,
+            Err(e) => {
+                let versions = cache::snapshot_versions();
+                let mut status = format!("{} {:#}", self.icons.error, e);
+                if !versions.is_empty() {
+                    status.push_str(&format!("; cached: {}", summarize(&versions)));
+                }
+                self.set_status(status);
+                return;
+            }
+        };
+
+        self.apply_list_op(ListOp::NewSince {
+            version: version.to_string(),
+            known,
+        });
+        self.set_status(format!(
+            "{} crates added since dataset v{} (now v{})",
+            self.filtered_crates.len(),
+            version,
+            self.metadata.version
+        ));
+    }
+
+    /// Narrow the list with a file of crate names: keep only those names (in file order) for an
+    /// allowlist, or drop them for a denylist. Names missing from the dataset are reported.
+    fn apply_name_list(&mut self, path: &str, allow: bool) {
+        let kind = if allow { "allowlist" } else { "denylist" };
+        let path = expand_home(path);
+        let names = match filter::read_name_list(&path) {
+            Ok(names) => names,
+            Err(e) => {
+                self.set_status(format!(
+                    "{} Could not read {} {}: {}",
+                    self.icons.error,
+                    kind,
+                    path.display(),
+                    e
+                ));
+                return;
+            }
+        };
+
+        let known: HashSet<String> = self
+            .all_crates
+            .iter()
+            .map(|c| c.name.to_lowercase())
+            .collect();
+        let missing: Vec<String> = names
+            .iter()
+            .filter(|name| !known.contains(*name))
+            .cloned()
+            .collect();
+
+        self.apply_list_op(if allow {
+            ListOp::Allow(names)
+        } else {
+            ListOp::Deny(names.into_iter().collect())
+        });
+        let mut status = format!(
+            "Applied {}: showing {} crates",
+            kind,
+            self.filtered_crates.len()
+        );
+        if !missing.is_empty() {
+            status.push_str(&format!(
+                "; {} not in dataset: {}",
+                missing.len(),
+                summarize(&missing)
+            ));
+        }
+        self.set_status(status);
+    }
+
+    /// Report how many crates a filter would show, without changing the list.
+    ///
+    /// Filters that start over (`core`, `search ...`) count over every crate; ones that
+    /// narrow (`mindl`, `maxdl`, `invert`) count over the current list, as the commands would.
+    fn count_matches(&mut self, args: &[&str]) {
+        const USAGE: &str = "Usage: :count <core|favs|nodocs|nohome|age B|search Q|search! Q|mindl N|maxdl N|invert>";
+
+        let op = match args {
+            ["core"] => ListOp::Core,
+            ["favs"] => ListOp::Favorites(self.favorites.ids()),
+            ["nodocs"] => ListOp::NoDocs,
+            ["nohome"] => ListOp::NoHome,
+            ["invert"] => ListOp::Invert,
+            ["age", bucket] => match AgeBucket::parse(bucket) {
+                Some(bucket) => ListOp::Age(bucket),
+                None => return self.set_status(USAGE),
+            },
+            ["search", query @ ..] if !query.is_empty() => {
+                ListOp::Fuzzy(query.join(" ").to_lowercase())
+            }
+            ["search!", query @ ..] if !query.is_empty() => {
+                ListOp::Search(query.join(" ").to_lowercase())
+            }
+            ["mindl" | "maxdl", n] => match parse_number(n) {
+                Some(n) if args[0] == "mindl" => ListOp::MinDownloads(n),
+                Some(n) => ListOp::MaxDownloads(n),
+                None => return self.set_status(USAGE),
+            },
+            _ => return self.set_status(USAGE),
+        };
+
+        let count = op
+            .apply(&self.all_crates, self.filtered_crates.clone())
+            .len();
+        self.set_status(format!("{} crates match {}", count, op.label()));
+    }
+
+    /// Open a popup describing where the cache lives and how fresh it is.
+    fn show_cache_info(&mut self) {
+        let info = match cache::cache_info() {
+            Ok(info) => info,
+            Err(e) => {
+                self.set_status(format!(
+                    "{} Could not read cache info: {}",
+                    self.icons.error, e
+                ));
+                return;
+            }
+        };
+
+        let mut lines = vec![
+            format!("Path:     {}", info.path.display()),
+            format!(
+                "Size:     {}",
+                info.size
+                    .map(format_bytes)
+                    .unwrap_or_else(|| "no cache file".to_string())
             ),
-            Span::styled(
-                "🐀 RATCRATE TUI",
-                Style::default()
-                    .fg(Color::Yellow)
-                    .add_modifier(Modifier::BOLD),
+            format!(
+                "Age:      {}",
+                info.age
+                    .map(format_duration)
+                    .unwrap_or_else(|| "unknown".to_string())
             ),
-            Span::styled(
-                "  - Ratatui Ecosystem Explorer   ║",
+            format!(
+                "Stale:    {}",
+                if info.stale {
+                    "yes (will download on next start)"
+                } else {
+                    "no"
+                }
+            ),
+            format!("TTL:      {}", format_duration(info.max_age)),
+            format!("Remote:   {}", info.remote_url),
+        ];
+        if !self.persistent {
+            lines.push("Temporary: the cache directory was not writable; nothing persists".into());
+        }
+
+        self.set_status(format!("Cache: {}", info.path.display()));
+        self.popup = Some(Popup::text("Cache Info", lines));
+    }
+
+    /// The selected crate's link of this kind, if present and a valid http(s) URL.
+    ///
+    /// Reports why in the status bar when there is nothing usable.
+    fn link_url(&mut self, kind: LinkKind) -> Option<url::Url> {
+        let Some(crate_pkg) = self.selected_crate() else {
+            self.set_status("No crate selected");
+            return None;
+        };
+        let Some(raw) = kind.get(crate_pkg).map(str::to_string) else {
+            self.set_status(format!("No {} URL for this crate", kind.label()));
+            return None;
+        };
+
+        match links::validate(&raw) {
+            Ok(url) => Some(url),
+            Err(e) => {
+                self.set_status(format!(
+                    "{} Invalid {} URL: {}",
+                    self.icons.error,
+                    kind.label(),
+                    e
+                ));
+                None
+            }
+        }
+    }
+
+    /// Open the selected crate's link in the browser.
+    fn open_link(&mut self, kind: LinkKind) {
+        let Some(url) = self.link_url(kind) else {
+            return;
+        };
+        match links::open(&url) {
+            Ok(()) => self.set_status(format!("Opening {}", url)),
+            Err(e) => self.set_status(format!(
+                "{} Could not launch browser: {}",
+                self.icons.error, e
+            )),
+        }
+    }
+
+    /// Open every distinct, valid link of the selected crate, up to `MAX_OPEN_LINKS`.
+    fn open_all_links(&mut self) {
+        let Some(crate_pkg) = self.selected_crate() else {
+            self.set_status("No crate selected");
+            return;
+        };
+
+        let mut urls: Vec<url::Url> = vec![];
+        let mut invalid = 0;
+        for raw in LinkKind::ALL.iter().filter_map(|kind| kind.get(crate_pkg)) {
+            match links::validate(raw) {
+                Ok(url) if !urls.contains(&url) => urls.push(url),
+                Ok(_) => {}
+                Err(_) => invalid += 1,
+            }
+        }
+        if urls.is_empty() {
+            self.set_status(format!("No valid links for {}", crate_pkg.name));
+            return;
+        }
+        let skipped = urls.len().saturating_sub(MAX_OPEN_LINKS);
+        urls.truncate(MAX_OPEN_LINKS);
+
+        let mut opened = 0;
+        for url in &urls {
+            if let Err(e) = links::open(url) {
+                self.set_status(format!(
+                    "{} Could not launch browser: {}",
+                    self.icons.error, e
+                ));
+                return;
+            }
+            opened += 1;
+        }
+
+        let mut status = format!(
+            "Opened {} link{}",
+            opened,
+            if opened == 1 { "" } else { "s" }
+        );
+        if invalid > 0 {
+            status.push_str(&format!(", skipped {} invalid", invalid));
+        }
+        if skipped > 0 {
+            status.push_str(&format!(", {} more over the limit", skipped));
+        }
+        self.set_status(status);
+    }
+
+    /// Copy `text` to the clipboard, reporting `what` was copied in the status bar.
+    fn copy_to_clipboard(&mut self, text: String, what: &str) {
+        match self.clipboard.copy(text) {
+            Ok(()) => self.set_status(format!("{} Copied {} to clipboard", self.icons.ok, what)),
+            Err(e) => self.set_status(format!("{} {:#}", self.icons.error, e)),
+        }
+    }
+
+    /// Copy the names in the current list, one per line, or as a single `cargo add` line.
+    fn copy_list(&mut self, install: bool) {
+        if self.filtered_crates.is_empty() {
+            self.set_status("Nothing to copy: the list is empty");
+            return;
+        }
+
+        let names: Vec<&str> = self
+            .filtered_crates
+            .iter()
+            .map(|c| c.name.as_str())
+            .collect();
+        let text = if install {
+            format!("cargo add {}", names.join(" "))
+        } else {
+            names.join("\n")
+        };
+        let what = if install {
+            format!("install line for {} crates", names.len())
+        } else {
+            format!("{} crate names", names.len())
+        };
+        self.copy_to_clipboard(text, &what);
+    }
+
+    /// Write the current list to `path` (default `./ratcrate-export.md`) as a Markdown table.
+    fn export_markdown(&mut self, path: Option<String>) {
+        if self.filtered_crates.is_empty() {
+            self.set_status("Nothing to export: the list is empty");
+            return;
+        }
+        let path = expand_home(path.as_deref().unwrap_or(EXPORT_MD_PATH));
+        match fs::write(&path, markdown_table(&self.filtered_crates)) {
+            Ok(()) => self.set_status(format!(
+                "{} Exported {} crates to {}",
+                self.icons.ok,
+                self.filtered_crates.len(),
+                path.display()
+            )),
+            Err(e) => self.set_status(format!(
+                "{} Cannot export to {}: {}",
+                self.icons.error,
+                path.display(),
+                e
+            )),
+        }
+    }
+
+    /// Copy crates.io and docs.rs badge Markdown for the selected crate.
+    fn copy_badges(&mut self) {
+        let Some(name) = self.selected_crate().map(|c| c.name.clone()) else {
+            self.set_status("No crate selected");
+            return;
+        };
+        self.copy_to_clipboard(badge_markdown(&name), &format!("badges for {}", name));
+    }
+
+    /// Copy the selected crate's `cargo add` line, or its crates.io URL.
+    fn copy_install(&mut self, url: bool) {
+        let Some(name) = self.selected_crate().map(|c| c.name.clone()) else {
+            self.set_status("No crate selected");
+            return;
+        };
+        if url {
+            self.copy_to_clipboard(
+                format!("https://crates.io/crates/{}", name),
+                &format!("crates.io URL for {}", name),
+            );
+        } else {
+            self.copy_to_clipboard(
+                format!("cargo add {}", name),
+                &format!("'cargo add {}'", name),
+            );
+        }
+    }
+
+    /// Copy a diagnostic block for bug reports: versions, data source, platform, terminal
+    /// and the current view and list.
+    fn copy_issue_report(&mut self) {
+        let report = self.issue_report(crossterm::terminal::size().ok());
+        self.copy_to_clipboard(report, "issue report");
+    }
+
+    /// The Markdown block `:report` copies; `size` is the terminal's columns and rows.
+    fn issue_report(&self, size: Option<(u16, u16)>) -> String {
+        let env_or_unset = |name: &str| std::env::var(name).unwrap_or_else(|_| "(unset)".into());
+        let source = match self.provenance {
+            Some(p) => format!(
+                "{}, {} old",
+                match p.origin {
+                    DataOrigin::Cache => "cache",
+                    DataOrigin::Download => "download",
+                },
+                format_duration(p.age())
+            ),
+            None => "unknown".to_string(),
+        };
+        let icons = if std::ptr::eq(self.icons, &icons::ASCII) {
+            "ascii"
+        } else {
+            "unicode"
+        };
+
+        let mut lines = vec![
+            "### Environment".to_string(),
+            format!(
+                "- {}: {}",
+                env!("CARGO_PKG_NAME"),
+                env!("CARGO_PKG_VERSION")
+            ),
+            format!(
+                "- dataset: {} (generated {}, {} crates)",
+                self.metadata.version,
+                self.metadata.generated_at,
+                self.all_crates.len()
+            ),
+            format!(
+                "- data: {}{}",
+                source,
+                if self.persistent {
+                    ""
+                } else {
+                    ", temporary cache dir"
+                }
+            ),
+            format!(
+                "- profile: {}",
+                cache::active_profile().unwrap_or("(default)")
+            ),
+            format!("- os: {} {}", std::env::consts::OS, std::env::consts::ARCH),
+            format!(
+                "- terminal: {}, TERM={}, COLORTERM={}, icons: {}",
+                size.map_or_else(
+                    || "unknown size".to_string(),
+                    |(w, h)| format!("{}x{}", w, h)
+                ),
+                env_or_unset("TERM"),
+                env_or_unset("COLORTERM"),
+                icons
+            ),
+            format!(
+                "- view: {:?}, list: {} ({} of {} crates)",
+                self.view,
+                self.describe_list(),
+                self.filtered_crates.len(),
+                self.all_crates.len()
+            ),
+            format!(
+                "- selected: {}",
+                self.selected_crate().map_or("(none)", |c| c.name.as_str())
+            ),
+        ];
+        if let Ok(ui) = toml::to_string(&self.ui_config) {
+            lines.push(String::new());
+            lines.push("```toml".to_string());
+            lines.push("[ui]".to_string());
+            lines.push(ui.trim_end().to_string());
+            lines.push("```".to_string());
+        }
+        lines.join("\n") + "\n"
+    }
+
+    /// Copy the current view as a plain-text report.
+    fn copy_view(&mut self) {
+        match self.view_text() {
+            Some((text, what)) => self.copy_to_clipboard(text, &what),
+            None => self.set_status("Nothing to copy in the help view"),
+        }
+    }
+
+    /// The current view as plain text, built from the data the view renders, plus a short
+    /// description for the status bar. `None` for views with nothing worth sharing.
+    fn view_text(&self) -> Option<(String, String)> {
+        let (lines, what) = match self.view {
+            View::List => {
+                let width = self
+                    .filtered_crates
+                    .iter()
+                    .map(|c| c.name.width())
+                    .max()
+                    .unwrap_or(0)
+                    .max("Crate".len());
+                let mut lines = vec![
+                    format!(
+                        "Crates ({})",
+                        match_count(
+                            self.filtered_crates.len(),
+                            self.all_crates.len(),
+                            self.count_as_percent
+                        )
+                    ),
+                    format!("{:<width$}  {:>10}  {:>10}", "Crate", "Downloads", "Weekly"),
+                ];
+                lines.extend(self.filtered_crates.iter().map(|c| {
+                    format!(
+                        "{}{}  {:>10}  {:>10}",
+                        c.name,
+                        " ".repeat(width - c.name.width()),
+                        format_number(c.downloads),
+                        format_number(c.recent_downloads)
+                    )
+                }));
+                (
+                    lines,
+                    format!("list of {} crates", self.filtered_crates.len()),
+                )
+            }
+            View::Stats => {
+                let [header, left, right] = stats_sections(self);
+                let text = header
+                    .into_iter()
+                    .chain(left)
+                    .chain([Line::from("")])
+                    .chain(distribution_lines(self))
+                    .chain(right)
+                    .chain(top_downloads_lines(self))
+                    .map(|line| line_text(&line))
+                    .collect();
+                (text, "stats report".to_string())
+            }
+            View::Categories => {
+                let mut lines = vec![format!("Categories ({})", self.categories.len())];
+                lines.extend(self.categories.iter().map(|(category, count)| {
+                    format!("{} ({})", self.category_label(category.as_deref()), count)
+                }));
+                (lines, format!("{} categories", self.categories.len()))
+            }
+            View::Help => return None,
+        };
+        Some((lines.join("\n").trim().to_string() + "\n", what))
+    }
+
+    /// Pop up the selected crate exactly as the dataset has it, as pretty JSON.
+    fn show_raw_json(&mut self) {
+        let Some(crate_pkg) = self.selected_crate() else {
+            self.set_status("No crate selected");
+            return;
+        };
+        match serde_json::to_string_pretty(crate_pkg) {
+            Ok(json) => {
+                let title = format!("{} (raw)", crate_pkg.name);
+                let lines = json.lines().map(str::to_string).collect();
+                self.popup = Some(Popup::scrolling(&title, lines));
+            }
+            Err(e) => self.set_status(format!(
+                "{} Cannot serialize crate: {}",
+                self.icons.error, e
+            )),
+        }
+    }
+
+    /// Open a popup with the app version, project links and data source.
+    fn show_about(&mut self) {
+        let heading = |text: String| {
+            Line::from(Span::styled(
+                text,
                 Style::default()
-                    .fg(Color::Cyan)
+                    .fg(Color::Green)
                     .add_modifier(Modifier::BOLD),
-            ),
-        ]),
-        Line::from(Span::styled(
-            "║                                                       ║",
-            Style::default()
-                .fg(Color::Cyan)
-                .add_modifier(Modifier::BOLD),
-        )),
-        Line::from(Span::styled(
-            "╚═══════════════════════════════════════════════════════╝",
-            Style::default()
-                .fg(Color::Cyan)
-                .add_modifier(Modifier::BOLD),
-        )),
-        Line::from(""),
-        Line::from(Span::styled(
-            "🎹 Navigation:",
-            Style::default()
-                .fg(Color::Green)
-                .add_modifier(Modifier::BOLD),
-        )),
-        Line::from(vec![
-            Span::styled("  j / ↓      ", Style::default().fg(Color::Cyan)),
-            Span::raw("- Move down"),
-        ]),
-        Line::from(vec![
-            Span::styled("  k / ↑      ", Style::default().fg(Color::Cyan)),
-            Span::raw("- Move up"),
-        ]),
-        Line::from(vec![
-            Span::styled("  Ctrl+d     ", Style::default().fg(Color::Cyan)),
-            Span::raw("- Page down"),
-        ]),
-        Line::from(vec![
-            Span::styled("  Ctrl+u     ", Style::default().fg(Color::Cyan)),
-            Span::raw("- Page up"),
-        ]),
-        Line::from(vec![
-            Span::styled("  g          ", Style::default().fg(Color::Cyan)),
-            Span::raw("- Go to top"),
-        ]),
-        Line::from(vec![
-            Span::styled("  G          ", Style::default().fg(Color::Cyan)),
-            Span::raw("- Go to bottom"),
-        ]),
-        Line::from(""),
-        Line::from(Span::styled(
-            "📑 Views:",
-            Style::default()
-                .fg(Color::Green)
-                .add_modifier(Modifier::BOLD),
-        )),
-        Line::from(vec![
-            Span::styled("  TAB        ", Style::default().fg(Color::Yellow)),
-            Span::raw("- Toggle Stats view"),
-        ]),
-        Line::from(vec![
-            Span::styled("  ?          ", Style::default().fg(Color::Yellow)),
-            Span::raw("- Toggle this help"),
-        ]),
-        Line::from(""),
-        Line::from(Span::styled(
-            "⚡ Commands (press ':'):",
-            Style::default()
-                .fg(Color::Green)
-                .add_modifier(Modifier::BOLD),
-        )),
-        Line::from(vec![
-            Span::styled("  :q, :quit         ", Style::default().fg(Color::Magenta)),
-            Span::raw("- Quit"),
-        ]),
-        Line::from(vec![
-            Span::styled("  :all              ", Style::default().fg(Color::Magenta)),
-            Span::raw("- Show all crates"),
-        ]),
+            ))
+        };
+        let entry = |label: &'static str, value: String| {
+            Line::from(vec![
+                Span::styled(label, Style::default().fg(Color::DarkGray)),
+                Span::styled(value, Style::default().fg(Color::Blue)),
+            ])
+        };
+
+        let logo = format!("{} RATCRATE TUI", self.icons.logo);
+        let lines = vec![
+            Line::from(vec![
+                Span::styled(
+                    format!("  {}", logo.trim_start()),
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    format!("  v{}", env!("CARGO_PKG_VERSION")),
+                    Style::default().fg(Color::Magenta),
+                ),
+            ]),
+            Line::from(Span::raw(format!("  {}", env!("CARGO_PKG_DESCRIPTION")))),
+            Line::from(""),
+            heading(format!("{} Links:", self.icons.links)),
+            entry("  Project:  ", env!("CARGO_PKG_REPOSITORY").to_string()),
+            entry("  Website:  ", "https://ratcrate.in".to_string()),
+            entry("  Data:     ", cache::remote_url().to_string()),
+            Line::from(""),
+            heading(format!("{} Credits:", self.icons.tip)),
+            Line::from(Span::raw(format!(
+                "  {}",
+                env!("CARGO_PKG_AUTHORS").replace(':', ", ")
+            ))),
+            Line::from(Span::styled(
+                "  Built with ratatui and crossterm",
+                Style::default().fg(Color::DarkGray),
+            )),
+        ];
+
+        self.popup = Some(Popup {
+            title: "About".to_string(),
+            lines,
+            picks: vec![],
+            selected: 0,
+            scroll: None,
+        });
+    }
+
+    /// Move the list offset so `scroll_margin` rows stay visible around the selection.
+    ///
+    /// `visible` is how many items fit in the list pane and `len` how many are drawn, both
+    /// captured during render. The margin shrinks on short panes so the selection can still
+    /// reach every row.
+    fn keep_selection_in_view(&mut self, visible: usize, len: usize) {
+        let Some(selected) = self.list_state.selected() else {
+            return;
+        };
+        if visible == 0 {
+            return;
+        }
+        let margin = self.scroll_margin.min((visible - 1) / 2);
+        let mut offset = self.list_state.offset();
+
+        if selected < offset + margin {
+            offset = selected.saturating_sub(margin);
+        } else if selected + margin >= offset + visible {
+            offset = selected + margin + 1 - visible;
+        }
+        let max_offset = len.saturating_sub(visible);
+        *self.list_state.offset_mut() = offset.min(max_offset);
+    }
+
+    fn next(&mut self) {
+        // Nothing to move through; an empty search must not select a phantom row
+        if self.filtered_crates.is_empty() {
+            return;
+        }
+        let i = match self.list_state.selected() {
+            Some(i) => {
+                if i >= self.filtered_crates.len() - 1 {
+                    0
+                } else {
+                    i + 1
+                }
+            }
+            None => 0,
+        };
+        self.list_state.select(Some(i));
+    }
+
+    fn previous(&mut self) {
+        if self.filtered_crates.is_empty() {
+            return;
+        }
+        let i = match self.list_state.selected() {
+            Some(i) => {
+                if i == 0 {
+                    self.filtered_crates.len() - 1
+                } else {
+                    i - 1
+                }
+            }
+            None => 0,
+        };
+        self.list_state.select(Some(i));
+    }
+
+    fn next_page(&mut self) {
+        if self.filtered_crates.is_empty() {
+            return;
+        }
+        let jump = 10;
+        let i = match self.list_state.selected() {
+            Some(i) => {
+                if i + jump >= self.filtered_crates.len() {
+                    self.filtered_crates.len() - 1
+                } else {
+                    i + jump
+                }
+            }
+            None => 0,
+        };
+        self.list_state.select(Some(i));
+    }
+
+    fn previous_page(&mut self) {
+        if self.filtered_crates.is_empty() {
+            return;
+        }
+        let jump = 10;
+        let i = match self.list_state.selected() {
+            Some(i) => i.saturating_sub(jump),
+            None => 0,
+        };
+        self.list_state.select(Some(i));
+    }
+
+    //     fn execute_command(&mut self) {
+    //         let cmd = self.command_input.trim();
+    //
+    //         if cmd.is_empty() {
+    //             self.mode = Mode::Normal;
+    //             return;
+    //         }
+    //
+    //         // Parse command
+    //         let parts: Vec<&str> = cmd.split_whitespace().collect();
+    //         let command = parts[0];
+    //
+    //         match command {
+    //             "q" | "quit" => {
+    //                 // Will be handled in main loop
+    //             }
+    //             "core" => {
+    //                 self.filtered_crates = self.all_crates
+    //                     .iter()
+    //                     .filter(|c| c.is_core_library)
+    //                     .cloned()
+    //                     .collect();
+    //                 self.list_state.select(Some(0));
+    //                 self.status_message = format!("Showing {} core libraries", self.filtered_crates.len());
+    //             }
+    //             "all" => {
+    //                 self.filtered_crates = self.all_crates.clone();
+    //                 self.list_state.select(Some(0));
+    //                 self.status_message = format!("Showing all {} crates", self.filtered_crates.len());
+    //             }
+    //             "top" => {
+    //                 let limit: usize = parts.get(1)
+    //                     .and_then(|s| s.parse().ok())
+    //                     .unwrap_or(10);
+    //
+    //                 let mut sorted = self.all_crates.clone();
+    //                 sorted.sort_by(|a, b| b.downloads.cmp(&a.downloads));
+    //                 self.filtered_crates = sorted.into_iter().take(limit).collect();
+    //                 self.list_state.select(Some(0));
+    //                 self.status_message = format!("Showing top {} by downloads", limit);
+    //             }
+    //             "recent" => {
+    //                 let limit: usize = parts.get(1)
+    //                     .and_then(|s| s.parse().ok())
+    //                     .unwrap_or(10);
+    //
+    //                 let mut sorted = self.all_crates.clone();
+    //                 sorted.sort_by(|a, b| b.recent_downloads.cmp(&a.recent_downloads));
+    //                 self.filtered_crates = sorted.into_iter().take(limit).collect();
+    //                 self.list_state.select(Some(0));
+    //                 self.status_message = format!("Showing top {} by weekly downloads", limit);
+    //             }
+    //             "new" => {
+    //                 let limit: usize = parts.get(1)
+    //                     .and_then(|s| s.parse().ok())
+    //                     .unwrap_or(10);
+    //
+    //                 let mut sorted = self.all_crates.clone();
+    //                 sorted.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    //                 self.filtered_crates = sorted.into_iter().take(limit).collect();
+    //                 self.list_state.select(Some(0));
+    //                 self.status_message = format!("Showing {} newest crates", limit);
+    //             }
+    //             "search" | "/" => {
+    //                 if parts.len() > 1 {
+    //                     let query = parts[1..].join(" ").to_lowercase();
+    //                     self.last_search = query.clone();
+    //                     self.filtered_crates = self.all_crates
+    //                         .iter()
+    //                         .filter(|c| {
+    //                             c.name.to_lowercase().contains(&query)
+    //                                 || c.description.to_lowercase().contains(&query)
+    //                         })
+    //                         .cloned()
+    //                         .collect();
+    //                     self.list_state.select(Some(0));
+    //                     self.status_message = format!(
+    //                         "Found {} crates matching '{}'",
+    //                         self.filtered_crates.len(),
+    //                         self.last_search
+    //                     );
+    //                 } else {
+    //                     self.status_message = "Usage: :search <query> or /<query>".to_string();
+    //                 }
+    //             }
+    //             "help" | "?" => {
+    //                 self.view = if self.view == View::Help { View::List } else { View::Help };
+    //                 self.status_message = if self.view == View::Help {
+    //                     "Showing help - Press ? or TAB to go back".to_string()
+    //                 } else {
+    //                     "Help hidden".to_string()
+    //                 };
+    //             }
+    //             "try" => {
+    //                 if let Some(crate_pkg) = self.selected_crate().cloned() {
+    //                     self.try_crate = Some(crate_pkg.name.clone());
+    //                     self.mode = Mode::Try;
+    //                     self.status_message = format!(
+    //                         "Try '{}' in /tmp/ratcrate-try? Press 'y' to confirm, 'n' to cancel",
+    //                         crate_pkg.name
+    //                     );
+    //                 } else {
+    //                     self.status_message = "No crate selected".to_string();
+    //                 }
+    //             }
+    //             _ => {
+    //                 // Try as search query
+    //                 let query = cmd.to_lowercase();
+    //                 self.last_search = query.clone();
+    //                 self.filtered_crates = self.all_crates
+    //                     .iter()
+    //                     .filter(|c| {
+    //                         c.name.to_lowercase().contains(&query)
+    //                             || c.description.to_lowercase().contains(&query)
+    //                     })
+    //                     .cloned()
+    //                     .collect();
+    //                 self.list_state.select(Some(0));
+    //                 self.status_message = format!(
+    //                     "Found {} crates matching '{}'",
+    //                     self.filtered_crates.len(),
+    //                     query
+    //                 );
+    //             }
+    //         }
+    //
+    //         self.command_input.clear();
+    //         self.mode = Mode::Normal;
+    //     }
+    //
+
+    fn execute_command(&mut self) {
+        let cmd = self.command_input.trim().to_string();
+
+        if cmd.is_empty() {
+            self.mode = Mode::Normal;
+            return;
+        }
+
+        // Parse command
+        let parts: Vec<&str> = cmd.split_whitespace().collect();
+        let command = parts[0];
+
+        match command {
+            "q" | "quit" => {
+                // Will be handled in main loop
+            }
+            "core" => {
+                self.apply_list_op(ListOp::Core);
+                self.set_status(format!(
+                    "Showing {} core libraries",
+                    self.filtered_crates.len()
+                ));
+            }
+            "all" => {
+                self.apply_list_op(ListOp::All);
+                self.set_status(format!("Showing all {} crates", self.filtered_crates.len()));
+            }
+            "sort" => match (parts.len() > 1).then(|| SortOrder::parse(&parts[1..].join(" "))) {
+                Some(Ok(order)) => {
+                    self.apply_list_op(ListOp::Sort(order));
+                    self.set_status(format!("Sorted by {}", order.label()));
+                }
+                Some(Err(e)) => self.set_status(e),
+                None => self.set_status("Usage: :sort <key> [asc|desc] [then <key> [asc|desc]]"),
+            },
+            "allowlist" | "denylist" => {
+                if parts.len() < 2 {
+                    self.set_status(format!("Usage: :{} <path>", command));
+                } else {
+                    // Rejoin so paths with spaces work
+                    let path = parts[1..].join(" ");
+                    self.apply_name_list(&path, command == "allowlist");
+                }
+            }
+            "newsince" => match parts.get(1) {
+                Some(version) => self.show_new_since(version),
+                None => self.set_status("Usage: :newsince <version>"),
+            },
+            "count" => {
+                self.count_matches(&parts[1..]);
+            }
+            "age" => match parts.get(1).and_then(|b| AgeBucket::parse(b)) {
+                Some(bucket) => {
+                    self.apply_list_op(ListOp::Age(bucket));
+                    let undated = self
+                        .all_crates
+                        .iter()
+                        .filter(|c| filter::parse_date(&c.created_at).is_none())
+                        .count();
+                    let mut status = format!(
+                        "Showing {} crates created {} ago",
+                        self.filtered_crates.len(),
+                        bucket.range()
+                    );
+                    if undated > 0 {
+                        status.push_str(&format!(" ({} with unknown dates excluded)", undated));
+                    }
+                    self.set_status(status);
+                }
+                None => self.set_status("Usage: :age <new|recent|mature|old>"),
+            },
+            "nodocs" => {
+                self.apply_list_op(ListOp::NoDocs);
+                self.set_status(format!(
+                    "{} crates have no documentation link",
+                    self.filtered_crates.len()
+                ));
+            }
+            "nohome" => {
+                self.apply_list_op(ListOp::NoHome);
+                self.set_status(format!(
+                    "{} crates have no homepage link",
+                    self.filtered_crates.len()
+                ));
+            }
+            "top" => {
+                let limit: usize = parts.get(1).and_then(|s| s.parse().ok()).unwrap_or(10);
+
+                self.apply_list_op(ListOp::Top(limit));
+                self.set_status(format!("Showing top {} by downloads", limit));
+            }
+            "recent" => {
+                let limit: usize = parts.get(1).and_then(|s| s.parse().ok()).unwrap_or(10);
+
+                self.apply_list_op(ListOp::Recent(limit));
+                self.set_status(format!("Showing top {} by weekly downloads", limit));
+            }
+            "new" => {
+                let limit: usize = parts.get(1).and_then(|s| s.parse().ok()).unwrap_or(10);
+
+                self.apply_list_op(ListOp::New(limit));
+                self.set_status(format!("Showing {} newest crates", limit));
+            }
+            "search" | "/" | "search!" => {
+                if parts.len() > 1 {
+                    let query = parts[1..].join(" ").to_lowercase();
+                    self.last_search = query.clone();
+                    self.apply_list_op(if command == "search!" {
+                        ListOp::Search(query)
+                    } else {
+                        ListOp::Fuzzy(query)
+                    });
+                    self.set_status(format!(
+                        "Found {} crates matching '{}'",
+                        self.filtered_crates.len(),
+                        self.last_search
+                    ));
+                } else {
+                    self.set_status("Usage: :search <query>, :search! <exact text> or /<query>");
+                }
+            }
+            "refresh" => {
+                self.start_refresh();
+            }
+            "clear-cache" => self.clear_cache(),
+            "invert" => {
+                self.invert_filter();
+            }
+            "prev" => {
+                self.previous_list();
+            }
+            "compat" => {
+                if parts.len() > 1 {
+                    self.filter_compat(&parts[1..].join(" "));
+                } else {
+                    self.set_status("Usage: :compat <requirement> (e.g. ^0.26)");
+                }
+            }
+            "mindl" | "maxdl" => {
+                self.filter_downloads(parts.get(1).copied(), command == "mindl");
+            }
+            "raw" => self.show_raw_json(),
+            "about" => {
+                self.show_about();
+            }
+            "copyview" => self.copy_view(),
+            "badges" => self.copy_badges(),
+            "report" => self.copy_issue_report(),
+            "copylist" => match parts.get(1).copied() {
+                None => self.copy_list(false),
+                Some("install") => self.copy_list(true),
+                Some(_) => self.set_status("Usage: :copylist [install]"),
+            },
+            "export" => match parts.get(1).copied() {
+                Some("md") => {
+                    let path = (parts.len() > 2).then(|| parts[2..].join(" "));
+                    self.export_markdown(path);
+                }
+                _ => self.set_status("Usage: :export md [path]"),
+            },
+            "mutecore" => {
+                self.mute_core = !self.mute_core;
+                self.set_status(if self.mute_core {
+                    "Core libraries styled like other crates"
+                } else {
+                    "Core libraries highlighted"
+                });
+            }
+            "fav" => {
+                self.toggle_favorite();
+            }
+            "favs" => {
+                self.show_favorites();
+            }
+            "open" if parts.get(1) == Some(&"all") => self.open_all_links(),
+            "open" => match parts
+                .get(1)
+                .map_or(Some(LinkKind::Repository), |k| LinkKind::parse(k))
+            {
+                Some(kind) => self.open_link(kind),
+                None => self.set_status("Usage: :open [repo|docs|home|all]"),
+            },
+            "cacheinfo" => {
+                self.show_cache_info();
+            }
+            "goto" => {
+                if parts.len() > 1 {
+                    let name = parts[1..].join(" ");
+                    self.goto(&name);
+                } else {
+                    self.set_status("Usage: :goto <crate name>");
+                }
+            }
+            "desc" => match parts.get(1).copied() {
+                Some("full") => self.set_full_description(true),
+                Some("short") => self.set_full_description(false),
+                None => self.set_full_description(!self.full_description),
+                Some(_) => self.set_status("Usage: :desc [full|short]"),
+            },
+            "categories" => {
+                self.toggle_categories();
+            }
+            "category" => {
+                if parts.len() > 1 {
+                    self.filter_category(&parts[1..].join(" "));
+                } else {
+                    self.list_categories();
+                }
+            }
+            "viewed" => {
+                self.show_recently_viewed();
+            }
+            "cleardeltas" => {
+                self.download_deltas.clear();
+                self.set_status("Cleared download deltas");
+            }
+            "tags" => {
+                self.show_crate_categories();
+            }
+            "reset" => match parts.get(1).copied() {
+                None => self.confirm_reset(false),
+                Some("all") => self.confirm_reset(true),
+                Some(_) => self.set_status("Usage: :reset [all]"),
+            },
+            "help" | "?" => {
+                self.view = if self.view == View::Help {
+                    View::List
+                } else {
+                    View::Help
+                };
+                self.set_status(if self.view == View::Help {
+                    "Showing help - Press ? or TAB to go back"
+                } else {
+                    "Help hidden"
+                });
+            }
+            "try" => match self.selected_crate() {
+                Some(c) => {
+                    let plan = Plan::try_crate(&c.name);
+                    self.confirm_plan(plan);
+                }
+                None => self.set_status("No crate selected"),
+            },
+            "add" => {
+                let dir = if parts.len() > 1 {
+                    expand_home(&parts[1..].join(" "))
+                } else {
+                    PathBuf::from(".")
+                };
+                let plan = match self.selected_crate() {
+                    Some(c) => Plan::add_to(&c.name, &dir),
+                    None => {
+                        self.set_status("No crate selected");
+                        return self.finish_command();
+                    }
+                };
+                match plan {
+                    Ok(plan) => self.confirm_plan(plan),
+                    Err(e) => self.set_status(format!("{} Cannot add: {}", self.icons.error, e)),
+                }
+            }
+            _ => {
+                // Try as search query
+                let query = cmd.to_lowercase();
+                self.last_search = query.clone();
+                self.apply_list_op(ListOp::Fuzzy(query.clone()));
+                self.set_status(format!(
+                    "Found {} crates matching '{}'",
+                    self.filtered_crates.len(),
+                    query
+                ));
+            }
+        }
+
+        self.finish_command();
+    }
+
+    /// Clear the typed command, staying in Confirm mode if the command asked a question.
+    fn finish_command(&mut self) {
+        self.command_input.clear();
+        self.search_preview = None;
+        if self.mode != Mode::Confirm {
+            self.mode = Mode::Normal;
+        }
+    }
+}
+
+// ============================================================================
+// UI Rendering
+// ============================================================================
+
+fn ui(f: &mut Frame, app: &mut App) {
+    let log_height = if app.show_log { LOG_PANE_HEIGHT } else { 0 };
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),             // Main content
+            Constraint::Length(log_height), // Status log (when expanded)
+            Constraint::Length(3),          // Command/status bar
+        ])
+        .split(f.area());
+
+    let main_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage(35), // List
+            Constraint::Percentage(65), // Detail
+        ])
+        .split(chunks[0]);
+
+    // Render list
+    render_list(f, app, main_chunks[0]);
+
+    // Render detail/help/stats based on view
+    match app.view {
+        View::List => render_detail(f, app, main_chunks[1]),
+        View::Help => render_help(f, app, main_chunks[1]),
+        View::Stats => render_stats(f, app, main_chunks[1]),
+        View::Categories => render_categories(f, app, main_chunks[1]),
+    }
+
+    // Render status log and command/status bar
+    if app.show_log {
+        render_log(f, app, chunks[1]);
+    }
+    render_command_bar(f, app, chunks[2]);
+
+    // Popups draw over everything else
+    render_popup(f, app);
+}
+
+/// Compute a rectangle of the given percentage size centered in `area`.
+fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+    let vertical = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(area);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(vertical[1])[1]
+}
+
+fn render_popup(f: &mut Frame, app: &App) {
+    let Some(popup) = &app.popup else {
+        return;
+    };
+    let area = centered_rect(70, 50, f.area());
+
+    let picker = !popup.picks.is_empty();
+    let mut lines = vec![Line::from("")];
+    lines.extend(popup.lines.iter().enumerate().map(|(i, line)| {
+        if picker && i == popup.selected {
+            line.clone().patch_style(app.theme.highlight())
+        } else {
+            line.clone()
+        }
+    }));
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        if picker {
+            format!(
+                "  j/k and Enter or 1-{} to choose, any other key to close",
+                popup.picks.len().min(9)
+            )
+        } else if popup.scroll.is_some() {
+            "  j/k or PgUp/PgDn to scroll, Esc to close".to_string()
+        } else {
+            "  Press any key to close".to_string()
+        },
+        Style::default().fg(Color::DarkGray),
+    )));
+
+    // Scroll long pickers so the selected row stays in view
+    let inner_height = app.theme.block().inner(area).height as usize;
+    let scroll = if picker {
+        (popup.selected + 2).saturating_sub(inner_height)
+    } else {
+        popup.scroll.unwrap_or(0)
+    };
+
+    let paragraph = Paragraph::new(lines)
+        .scroll((scroll as u16, 0))
+        .block(
+            app.theme
+                .block()
+                .border_style(Style::default().fg(Color::Yellow))
+                .title(Span::styled(
+                    format!(" {} ", popup.title),
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                )),
+        )
+        .style(app.theme.text())
+        .wrap(Wrap { trim: false });
+
+    f.render_widget(Clear, area);
+    f.render_widget(paragraph, area);
+}
+
+/// Share of all-time downloads that were recent for a crate to count as growing
+const GROWING_RATIO: f64 = 0.2;
+
+/// Momentum heuristic for the list: how much of a crate's total is recent downloads
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Trend {
+    Growing,
+    Stable,
+}
+
+impl Trend {
+    /// `None` for crates without downloads, where the ratio means nothing.
+    fn of(c: &CratePackage) -> Option<Self> {
+        if c.downloads == 0 {
+            return None;
+        }
+        let ratio = c.recent_downloads as f64 / c.downloads as f64;
+        Some(if ratio >= GROWING_RATIO {
+            Trend::Growing
+        } else {
+            Trend::Stable
+        })
+    }
+
+    fn span(self, icons: &Icons) -> Span<'static> {
+        match self {
+            Trend::Growing => Span::styled(
+                format!(" {}", icons.growing),
+                Style::default().fg(Color::Green),
+            ),
+            Trend::Stable => Span::styled(
+                format!(" {}", icons.stable),
+                Style::default().fg(Color::DarkGray),
+            ),
+        }
+    }
+}
+
+/// The list title's match count: `matched/total`, or the matched share of the dataset
+fn match_count(matched: usize, total: usize, percent: bool) -> String {
+    if !percent {
+        return format!("{}/{}", matched, total);
+    }
+    if total == 0 {
+        return "0%".to_string();
+    }
+    let share = matched as f64 * 100.0 / total as f64;
+    // One decimal keeps small filters from collapsing to 0%
+    if share < 10.0 && matched > 0 {
+        format!("{:.1}%", share)
+    } else {
+        format!("{:.0}%", share)
+    }
+}
+
+fn render_list(f: &mut Frame, app: &mut App, area: Rect) {
+    let orphans = if app.favorites_only() {
+        app.orphaned_favorites().len()
+    } else {
+        0
+    };
+    let rows_per_item = if app.compact_list { 1 } else { 2 };
+    let inner = app.theme.block().inner(area);
+    let visible = inner.height as usize / rows_per_item;
+    let len = app.filtered_crates.len() + orphans;
+    app.keep_selection_in_view(visible, len);
+
+    // Only the rows that fit are built; the widget gets a window-relative selection
+    let start = app.list_state.offset().min(len);
+    let end = (start + visible).min(len);
+    let shown = app.filtered_crates.len();
+
+    let icons = app.icons;
+    // Columns left for an item after the selection marker; names are cut to fit
+    let item_width =
+        (inner.width as usize).saturating_sub(app.theme.highlight_symbol(icons.selected).width());
+    let gutter_width = if app.gutter {
+        icons.favorite.width().max(icons.visited.width()) + 1
+    } else {
+        0
+    };
+
+    let mut items: Vec<ListItem> = app.filtered_crates[start.min(shown)..end.min(shown)]
+        .iter()
+        .map(|crate_pkg| {
+            let core = app.highlights_core(crate_pkg);
+            let icon = if core { icons.core } else { icons.crates };
+            let favorite = app.favorites.contains(&crate_pkg.id);
+            let mut stats_spans = vec![
+                Span::styled(
+                    format!("{} ", icons.downloads),
+                    Style::default().fg(Color::Green),
+                ),
+                Span::styled(
+                    format_number(crate_pkg.downloads),
+                    Style::default().fg(Color::Green),
+                ),
+            ];
+            stats_spans.extend(app.download_delta_span(&crate_pkg.id));
+            stats_spans.extend([
+                Span::styled(
+                    format!(" {} ", icons.weekly),
+                    Style::default().fg(Color::Blue),
+                ),
+                Span::styled(
+                    format_number(crate_pkg.recent_downloads),
+                    Style::default().fg(Color::Blue),
+                ),
+            ]);
+            if app.trend_markers {
+                stats_spans.extend(Trend::of(crate_pkg).map(|t| t.span(icons)));
+            }
+
+            // Favorites are starred in the gutter when it's shown, after the name otherwise
+            let trailing_star = favorite && !app.gutter;
+
+            // Whatever shares the name's line is reserved first; the name gets the rest
+            let mut reserved = gutter_width + icon.width() + 1;
+            if trailing_star {
+                reserved += 1 + icons.favorite.width();
+            }
+            if app.compact_list {
+                reserved += 2 + stats_spans.iter().map(Span::width).sum::<usize>();
+            }
+            let name_width = item_width.saturating_sub(reserved);
+
+            // Create a colorful list item
+            let mut name_spans = vec![
+                Span::styled(
+                    format!("{} ", icon),
+                    if core {
+                        Style::default().fg(Color::Yellow)
+                    } else {
+                        Style::default().fg(Color::Cyan)
+                    },
+                ),
+                Span::styled(
+                    truncate_to_width(&crate_pkg.name, name_width),
+                    if core {
+                        Style::default()
+                            .fg(Color::Yellow)
+                            .add_modifier(Modifier::BOLD)
+                    } else {
+                        Style::default()
+                            .fg(Color::Cyan)
+                            .add_modifier(Modifier::BOLD)
+                    },
+                ),
+            ];
+            if trailing_star {
+                name_spans.push(Span::styled(
+                    format!(" {}", icons.favorite),
+                    Style::default().fg(Color::LightRed),
+                ));
+            }
+            if app.gutter {
+                let (marker, color) = if favorite {
+                    (icons.favorite, Color::LightRed)
+                } else if app.visited.contains(&crate_pkg.id) {
+                    (icons.visited, Color::DarkGray)
+                } else {
+                    ("", Color::Reset)
+                };
+                name_spans.insert(
+                    0,
+                    Span::styled(
+                        format!("{:<gutter_width$}", marker),
+                        Style::default().fg(color),
+                    ),
+                );
+            }
+            // Compact mode puts everything on one line; the default uses two
+            let content = if app.compact_list {
+                let mut spans = name_spans;
+                spans.push(Span::raw("  "));
+                spans.extend(stats_spans);
+                vec![Line::from(spans)]
+            } else {
+                let mut stats_line = vec![Span::raw(" ".repeat(gutter_width + 2))];
+                stats_line.extend(stats_spans);
+                vec![Line::from(name_spans), Line::from(stats_line)]
+            };
+
+            ListItem::new(content)
+        })
+        .collect();
+
+    // Orphaned favorites trail the list, dimmed and never selectable
+    if app.favorites_only() {
+        let window = start.saturating_sub(shown)..end.saturating_sub(shown);
+        items.extend(app.orphaned_favorites()[window].iter().map(|f| {
+            ListItem::new(Line::from(vec![
+                Span::raw(format!("{} {} ", icons.favorite, f.name)),
+                Span::styled("(not in dataset)", Style::default().fg(Color::DarkGray)),
+            ]))
+            .style(Style::default().add_modifier(Modifier::DIM))
+        }));
+    }
+
+    let mut title = vec![
+        Span::styled(
+            format!(" {} Crates ", icons.crates),
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            format!(
+                "({}) ",
+                match_count(
+                    app.filtered_crates.len(),
+                    app.all_crates.len(),
+                    app.count_as_percent
+                )
+            ),
+            Style::default().fg(Color::DarkGray),
+        ),
+    ];
+    if app.favorites_only() {
+        title.push(Span::styled(
+            format!("{} Favorites ", icons.favorite),
+            Style::default()
+                .fg(Color::LightRed)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+    if let Some(order) = app.active_sort() {
+        let step = |s: SortStep| {
+            format!(
+                "{} {}",
+                if s.descending { icons.down } else { icons.up },
+                s.key.name()
+            )
+        };
+        let mut label = step(order.primary);
+        if order.has_tiebreak() {
+            label = format!("{}, {}", label, step(order.then));
+        }
+        title.push(Span::styled(
+            format!("{} ", label),
+            Style::default().fg(Color::Magenta),
+        ));
+    }
+    if app.is_refreshing() {
+        title.push(Span::styled(
+            "refreshing... ",
+            Style::default().fg(Color::Yellow),
+        ));
+    }
+
+    // Dim the old contents while a refresh is in flight; they stay navigable.
+    let list_style = if app.is_refreshing() {
+        Style::default().add_modifier(Modifier::DIM)
+    } else {
+        Style::default()
+    };
+
+    let list = List::new(items)
+        .block(app.theme.block().title(title).style(Style::default()))
+        .style(list_style)
+        .highlight_style(app.theme.highlight())
+        .highlight_symbol(app.theme.highlight_symbol(icons.selected));
+
+    let mut window_state = ListState::default().with_selected(
+        app.list_state
+            .selected()
+            .and_then(|i| i.checked_sub(start))
+            .filter(|&i| i < end - start),
+    );
+    f.render_stateful_widget(list, area, &mut window_state);
+}
+
+fn render_detail(f: &mut Frame, app: &App, area: Rect) {
+    let icons = app.icons;
+    let detail = if let Some(crate_pkg) = app.selected_crate() {
+        let mut lines = vec![];
+
+        // Title with colorful icon
+        let core = app.highlights_core(crate_pkg);
+        let icon = if core { icons.core } else { icons.crates };
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("{} {} ", icon, crate_pkg.name),
+                Style::default()
+                    .fg(if core { Color::Yellow } else { Color::Cyan })
+                    .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+            ),
+            Span::styled(
+                format!("v{}", crate_pkg.version),
+                Style::default().fg(Color::Magenta),
+            ),
+        ]));
+
+        if core {
+            lines.push(Line::from(Span::styled(
+                format!("{} CORE LIBRARY {}", icons.core, icons.core),
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            )));
+        }
+
+        lines.push(Line::from(""));
+
+        // Description with nice formatting
+        lines.push(Line::from(Span::styled(
+            format!("{} Description:", icons.description),
+            Style::default()
+                .fg(Color::Green)
+                .add_modifier(Modifier::BOLD),
+        )));
+
+        // Wrap to the pane's inner width (minus the indent) so wrapped lines stay indented
+        let wrap_width = (app.theme.block().inner(area).width as usize).saturating_sub(2);
+        let mut description = wrap_words(&crate_pkg.description, wrap_width);
+        let limit = app.description_lines;
+        let truncated = !app.full_description && limit > 0 && description.len() > limit;
+        if truncated {
+            description.truncate(limit);
+            if let Some(last) = description.last_mut() {
+                // Make room for the ellipsis so it doesn't wrap onto a line of its own
+                while !last.is_empty() && last.width() + 1 > wrap_width {
+                    last.pop();
+                }
+                last.push('…');
+            }
+        }
+        for line in description {
+            lines.push(Line::from(Span::styled(
+                format!("  {}", line),
+                app.theme.text(),
+            )));
+        }
+        if truncated {
+            lines.push(Line::from(Span::styled(
+                "  (e or :desc full to expand)",
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+        lines.push(Line::from(""));
+
+        // Statistics with icons and colors
+        lines.push(Line::from(Span::styled(
+            format!("{} Statistics:", icons.stats),
+            Style::default()
+                .fg(Color::Green)
+                .add_modifier(Modifier::BOLD),
+        )));
+        let mut downloads = vec![
+            Span::raw("  "),
+            Span::styled(
+                format!("{} Downloads:       ", icons.downloads),
+                Style::default().fg(Color::DarkGray),
+            ),
+            Span::styled(
+                format_number(crate_pkg.downloads),
+                Style::default()
+                    .fg(Color::Green)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ];
+        downloads.extend(app.download_delta_span(&crate_pkg.id));
+        lines.push(Line::from(downloads));
+        lines.push(Line::from(vec![
+            Span::raw("  "),
+            Span::styled(
+                format!("{} Weekly:          ", icons.weekly),
+                Style::default().fg(Color::DarkGray),
+            ),
+            Span::styled(
+                format_number(crate_pkg.recent_downloads),
+                Style::default()
+                    .fg(Color::Blue)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ]));
+        lines.push(Line::from(""));
+
+        // The ratatui requirement, the most relevant fact in this ecosystem
+        let dependency = &crate_pkg.ratatui_dependency;
+        lines.push(Line::from(Span::styled(
+            format!("{} Ratatui:", icons.ratatui),
+            Style::default()
+                .fg(Color::Green)
+                .add_modifier(Modifier::BOLD),
+        )));
+        let version = dependency.version.trim();
+        lines.push(Line::from(vec![
+            Span::raw("  "),
+            Span::styled("Requires:  ", Style::default().fg(Color::DarkGray)),
+            if version.is_empty() {
+                Span::styled("unspecified", Style::default().fg(Color::DarkGray))
+            } else {
+                Span::styled(
+                    version.to_string(),
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD),
+                )
+            },
+            Span::styled(
+                format!("  ({})", dependency_kind(dependency)),
+                Style::default().fg(Color::Yellow),
+            ),
+        ]));
+        lines.push(Line::from(""));
+
+        // Dates, with a long-quiet crate dimmed so it stands out as possibly abandoned
+        lines.push(Line::from(Span::styled(
+            format!("{} Dates:", icons.dates),
+            Style::default()
+                .fg(Color::Green)
+                .add_modifier(Modifier::BOLD),
+        )));
+        let now = chrono::Utc::now();
+        for (label, raw, can_go_stale) in [
+            ("Updated:   ", &crate_pkg.updated_at, true),
+            ("Created:   ", &crate_pkg.created_at, false),
+        ] {
+            let (text, days) = relative_date(raw, now);
+            let style = match days {
+                Some(days) if can_go_stale && days >= STALE_AFTER_DAYS => Style::default()
+                    .fg(Color::DarkGray)
+                    .add_modifier(Modifier::DIM),
+                Some(_) => Style::default().fg(Color::Blue),
+                None => Style::default().fg(Color::DarkGray),
+            };
+            lines.push(Line::from(vec![
+                Span::raw("  "),
+                Span::styled(label, Style::default().fg(Color::DarkGray)),
+                Span::styled(text, style),
+            ]));
+        }
+        lines.push(Line::from(""));
+
+        // Install command with colorful box
+        lines.push(Line::from(Span::styled(
+            format!("{} Install:", icons.install),
+            Style::default()
+                .fg(Color::Green)
+                .add_modifier(Modifier::BOLD),
+        )));
+        lines.push(Line::from(vec![
+            Span::raw("  "),
+            Span::styled(
+                format!("cargo add {}", crate_pkg.name),
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                "  (y to copy, Y for the crates.io URL)",
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]));
+        lines.push(Line::from(""));
+
+        // Try mode hint
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("{} Tip: ", icons.tip),
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled("Use ", Style::default().fg(Color::DarkGray)),
+            Span::styled(
+                ":try",
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                " to test this crate in a temporary project!",
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]));
+        lines.push(Line::from(""));
+
+        // Links with icons
+        if crate_pkg.repository.is_some()
+            || crate_pkg.documentation.is_some()
+            || crate_pkg.homepage.is_some()
+        {
+            lines.push(Line::from(Span::styled(
+                format!("{} Links:", icons.links),
+                Style::default()
+                    .fg(Color::Green)
+                    .add_modifier(Modifier::BOLD),
+            )));
+
+            if let Some(repo) = &crate_pkg.repository {
+                lines.push(Line::from(vec![
+                    Span::raw("  "),
+                    Span::styled(
+                        format!("{} Repo:  ", icons.repo),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                    Span::styled(repo, Style::default().fg(Color::Blue)),
+                ]));
+            }
+            if let Some(docs) = &crate_pkg.documentation {
+                lines.push(Line::from(vec![
+                    Span::raw("  "),
+                    Span::styled(
+                        format!("{} Docs:  ", icons.docs),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                    Span::styled(docs, Style::default().fg(Color::Blue)),
+                ]));
+            }
+            if let Some(home) = &crate_pkg.homepage {
+                lines.push(Line::from(vec![
+                    Span::raw("  "),
+                    Span::styled(
+                        format!("{} Home:  ", icons.home),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                    Span::styled(home, Style::default().fg(Color::Blue)),
+                ]));
+            }
+            lines.push(Line::from(""));
+        }
+
+        // Categories with colorful tags
+        if let Some(categories) = &crate_pkg.categories
+            && !categories.is_empty()
+        {
+            lines.push(Line::from(Span::styled(
+                format!("{} Categories:", icons.categories),
+                Style::default()
+                    .fg(Color::Green)
+                    .add_modifier(Modifier::BOLD),
+            )));
+
+            let cat_spans: Vec<Span> = categories
+                .iter()
+                .flat_map(|cat| {
+                    vec![
+                        Span::styled("  [", Style::default().fg(Color::DarkGray)),
+                        Span::styled(
+                            cat,
+                            Style::default()
+                                .fg(Color::Magenta)
+                                .add_modifier(Modifier::BOLD),
+                        ),
+                        Span::styled(
+                            format!(
+                                " ({})",
+                                app.category_counts
+                                    .get(&filter::normalize_category(cat))
+                                    .copied()
+                                    .unwrap_or(0)
+                            ),
+                            Style::default().fg(Color::DarkGray),
+                        ),
+                        Span::styled("]", Style::default().fg(Color::DarkGray)),
+                        Span::raw(" "),
+                    ]
+                })
+                .collect();
+
+            lines.push(Line::from(cat_spans));
+        }
+
+        Text::from(lines)
+    } else {
+        Text::from(vec![
+            Line::from(""),
+            Line::from(Span::styled(
+                "No crate selected",
+                Style::default().fg(Color::DarkGray),
+            )),
+            Line::from(""),
+            Line::from(Span::styled(
+                format!("Use j/k or {} to navigate", icons.arrows),
+                Style::default().fg(Color::DarkGray),
+            )),
+        ])
+    };
+
+    let paragraph = Paragraph::new(detail)
+        .block(
+            app.theme
+                .block()
+                .title(Span::styled(
+                    format!(" {} Detail ", icons.detail),
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD),
+                ))
+                .style(Style::default()),
+        )
+        .wrap(Wrap { trim: false });
+
+    f.render_widget(paragraph, area);
+}
+
+/// Build a banner row `║   <content>   ║`, padding by display width so the right border
+/// lines up whatever icons are in use.
+fn banner_row<'a>(content: Vec<Span<'a>>) -> Line<'a> {
+    let border = Style::default()
+        .fg(Color::Cyan)
+        .add_modifier(Modifier::BOLD);
+    let content_width: usize = content.iter().map(|s| s.width()).sum();
+    let padding = BANNER_INNER_WIDTH.saturating_sub(content_width + 3);
+
+    let mut spans = vec![Span::styled("║   ", border)];
+    spans.extend(content);
+    spans.push(Span::styled(format!("{}║", " ".repeat(padding)), border));
+    Line::from(spans)
+}
+
+fn render_help(f: &mut Frame, app: &App, area: Rect) {
+    let icons = app.icons;
+    let logo = format!("{} RATCRATE TUI", icons.logo);
+    let help_text = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            "╔═══════════════════════════════════════════════════════╗",
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )),
+        banner_row(vec![]),
+        banner_row(vec![
+            Span::styled(
+                logo.trim_start().to_string(),
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                "  - Ratatui Ecosystem Explorer",
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ]),
+        banner_row(vec![]),
+        Line::from(Span::styled(
+            "╚═══════════════════════════════════════════════════════╝",
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+        Line::from(Span::styled(
+            format!("{} Navigation:", icons.navigation),
+            Style::default()
+                .fg(Color::Green)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(vec![
+            Span::styled(
+                format!("  j / {:<7}", icons.down),
+                Style::default().fg(Color::Cyan),
+            ),
+            Span::raw("- Move down"),
+        ]),
+        Line::from(vec![
+            Span::styled(
+                format!("  k / {:<7}", icons.up),
+                Style::default().fg(Color::Cyan),
+            ),
+            Span::raw("- Move up"),
+        ]),
+        Line::from(vec![
+            Span::styled("  Ctrl+d     ", Style::default().fg(Color::Cyan)),
+            Span::raw("- Page down"),
+        ]),
+        Line::from(vec![
+            Span::styled("  Ctrl+u     ", Style::default().fg(Color::Cyan)),
+            Span::raw("- Page up"),
+        ]),
+        Line::from(vec![
+            Span::styled("  g          ", Style::default().fg(Color::Cyan)),
+            Span::raw("- Go to top"),
+        ]),
+        Line::from(vec![
+            Span::styled("  G          ", Style::default().fg(Color::Cyan)),
+            Span::raw("- Go to bottom"),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled(
+            format!("{} Views:", icons.views),
+            Style::default()
+                .fg(Color::Green)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(vec![
+            Span::styled("  TAB        ", Style::default().fg(Color::Yellow)),
+            Span::raw("- Toggle Stats view"),
+        ]),
+        Line::from(vec![
+            Span::styled("  ?          ", Style::default().fg(Color::Yellow)),
+            Span::raw("- Toggle this help"),
+        ]),
+        Line::from(vec![
+            Span::styled("  C          ", Style::default().fg(Color::Yellow)),
+            Span::raw("- Browse categories (Enter filters)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  v          ", Style::default().fg(Color::Yellow)),
+            Span::raw("- Recently viewed crates (1-9 to jump)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  t          ", Style::default().fg(Color::Yellow)),
+            Span::raw("- All categories of the crate (Enter filters)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  o / O / H  ", Style::default().fg(Color::Yellow)),
+            Span::raw("- Open the repository / docs / homepage"),
+        ]),
+        Line::from(vec![
+            Span::styled("  A          ", Style::default().fg(Color::Yellow)),
+            Span::raw("- Open all of the crate's links"),
+        ]),
+        Line::from(vec![
+            Span::styled("  y / Y      ", Style::default().fg(Color::Yellow)),
+            Span::raw("- Copy the cargo add line / the crates.io URL"),
+        ]),
+        Line::from(vec![
+            Span::styled("  x          ", Style::default().fg(Color::Yellow)),
+            Span::raw("- Copy the current view as plain text"),
+        ]),
+        Line::from(vec![
+            Span::styled("  e          ", Style::default().fg(Color::Yellow)),
+            Span::raw("- Expand / shorten the description"),
+        ]),
+        Line::from(vec![
+            Span::styled("  r          ", Style::default().fg(Color::Yellow)),
+            Span::raw("- Reverse the current list"),
+        ]),
+        Line::from(vec![
+            Span::styled("  c          ", Style::default().fg(Color::Yellow)),
+            Span::raw("- Toggle compact one-line list"),
+        ]),
+        Line::from(vec![
+            Span::styled("  ' <letter> ", Style::default().fg(Color::Yellow)),
+            Span::raw("- Sort by name and jump to a letter (repeat for the next)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  *          ", Style::default().fg(Color::Yellow)),
+            Span::raw("- Next crate matching the last search"),
+        ]),
+        Line::from(vec![
+            Span::styled("  f / F      ", Style::default().fg(Color::Yellow)),
+            Span::raw("- Star selected crate / toggle favorites only"),
+        ]),
+        Line::from(vec![
+            Span::styled("  L          ", Style::default().fg(Color::Yellow)),
+            Span::raw("- Toggle status log (PgUp/PgDn to scroll)"),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled(
+            format!(
+                "{} Commands (press ':', Up/Down for history, Tab to complete):",
+                icons.commands
+            ),
+            Style::default()
+                .fg(Color::Green)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(vec![
+            Span::styled("  :q, :quit         ", Style::default().fg(Color::Magenta)),
+            Span::raw("- Quit"),
+        ]),
+        Line::from(vec![
+            Span::styled("  :all              ", Style::default().fg(Color::Magenta)),
+            Span::raw("- Show all crates"),
+        ]),
+        Line::from(vec![
+            Span::styled("  :core             ", Style::default().fg(Color::Magenta)),
+            Span::raw("- Show core libraries only"),
+        ]),
+        Line::from(vec![
+            Span::styled("  :top [N]          ", Style::default().fg(Color::Magenta)),
+            Span::raw("- Top N by downloads (default: 10)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  :recent [N]       ", Style::default().fg(Color::Magenta)),
+            Span::raw("- Top N by weekly downloads"),
+        ]),
+        Line::from(vec![
+            Span::styled("  :new [N]          ", Style::default().fg(Color::Magenta)),
+            Span::raw("- N newest crates"),
+        ]),
+        Line::from(vec![
+            Span::styled("  :search <query>   ", Style::default().fg(Color::Magenta)),
+            Span::raw("- Search crates"),
+        ]),
+        Line::from(vec![
+            Span::styled("  /<query>          ", Style::default().fg(Color::Magenta)),
+            Span::raw("- Live search: the list filters as you type (Enter keeps, Esc restores)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  :mindl <N>        ", Style::default().fg(Color::Magenta)),
+            Span::raw("- Keep crates with at least N downloads (50K, 1.5M)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  :maxdl <N>        ", Style::default().fg(Color::Magenta)),
+            Span::raw("- Keep crates with at most N downloads"),
+        ]),
+        Line::from(vec![
+            Span::styled("  :compat <req>     ", Style::default().fg(Color::Magenta)),
+            Span::raw("- Keep crates whose ratatui requirement fits (^0.26)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  :invert, i        ", Style::default().fg(Color::Magenta)),
+            Span::raw("- Show every crate the current filter hides"),
+        ]),
+        Line::from(vec![
+            Span::styled("  :fav, :favs       ", Style::default().fg(Color::Magenta)),
+            Span::raw("- Star selected crate / show favorites"),
+        ]),
+        Line::from(vec![
+            Span::styled("  :age <bucket>     ", Style::default().fg(Color::Magenta)),
+            Span::raw("- new, recent, mature or old crates"),
+        ]),
+        Line::from(vec![
+            Span::styled("  :category <name>  ", Style::default().fg(Color::Magenta)),
+            Span::raw("- Crates in a category (any casing)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  :nodocs, :nohome  ", Style::default().fg(Color::Magenta)),
+            Span::raw("- Crates missing a docs / homepage link"),
+        ]),
+        Line::from(vec![
+            Span::styled("  :sort <key>       ", Style::default().fg(Color::Magenta)),
+            Span::raw("- Sort the list (e.g. version, namelen-asc)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  :newsince <ver>   ", Style::default().fg(Color::Magenta)),
+            Span::raw("- Crates added since a cached dataset version"),
+        ]),
+        Line::from(vec![
+            Span::styled("  :allowlist <path> ", Style::default().fg(Color::Magenta)),
+            Span::raw("- Keep only the names listed in a file"),
+        ]),
+        Line::from(vec![
+            Span::styled("  :denylist <path>  ", Style::default().fg(Color::Magenta)),
+            Span::raw("- Hide the names listed in a file"),
+        ]),
+        Line::from(vec![
+            Span::styled("  :count <filter>   ", Style::default().fg(Color::Magenta)),
+            Span::raw("- Count matches without changing the list"),
+        ]),
+        Line::from(vec![
+            Span::styled("  :prev, Backspace  ", Style::default().fg(Color::Magenta)),
+            Span::raw("- Go back to the previous list"),
+        ]),
+        Line::from(vec![
+            Span::styled("  :goto <name>      ", Style::default().fg(Color::Magenta)),
+            Span::raw("- Jump to a crate by exact name"),
+        ]),
+        Line::from(vec![
+            Span::styled(
+                "  :open [repo|docs|home|all]",
+                Style::default().fg(Color::Magenta),
+            ),
+            Span::raw(" - Open one or all links of the selected crate"),
+        ]),
+        Line::from(vec![
+            Span::styled("  :reset [all]      ", Style::default().fg(Color::Magenta)),
+            Span::raw("- Clear favorites/session (all: also the cache)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  :refresh          ", Style::default().fg(Color::Magenta)),
+            Span::raw("- Reload data in the background"),
+        ]),
         Line::from(vec![
-            Span::styled("  :core             ", Style::default().fg(Color::Magenta)),
-            Span::raw("- Show core libraries only"),
+            Span::styled("  :clear-cache      ", Style::default().fg(Color::Magenta)),
+            Span::raw("- Delete a corrupted cache and download again"),
         ]),
         Line::from(vec![
-            Span::styled("  :top [N]          ", Style::default().fg(Color::Magenta)),
-            Span::raw("- Top N by downloads (default: 10)"),
+            Span::styled("  :copylist [install]", Style::default().fg(Color::Magenta)),
+            Span::raw(" - Copy listed names / a cargo add line"),
         ]),
         Line::from(vec![
-            Span::styled("  :recent [N]       ", Style::default().fg(Color::Magenta)),
-            Span::raw("- Top N by weekly downloads"),
+            Span::styled("  :export md [path] ", Style::default().fg(Color::Magenta)),
+            Span::raw("- Write the list as a Markdown table"),
         ]),
         Line::from(vec![
-            Span::styled("  :new [N]          ", Style::default().fg(Color::Magenta)),
-            Span::raw("- N newest crates"),
+            Span::styled("  :mutecore         ", Style::default().fg(Color::Magenta)),
+            Span::raw("- Toggle core-library highlighting"),
         ]),
         Line::from(vec![
-            Span::styled("  :search <query>   ", Style::default().fg(Color::Magenta)),
-            Span::raw("- Search crates"),
+            Span::styled("  :about            ", Style::default().fg(Color::Magenta)),
+            Span::raw("- Version, project links and data source"),
         ]),
         Line::from(vec![
-            Span::styled("  /<query>          ", Style::default().fg(Color::Magenta)),
-            Span::raw("- Quick search"),
+            Span::styled("  :cacheinfo        ", Style::default().fg(Color::Magenta)),
+            Span::raw("- Show cache location, age and freshness"),
+        ]),
+        Line::from(vec![
+            Span::styled("  :try              ", Style::default().fg(Color::Magenta)),
+            Span::raw("- Try selected crate in a scratch project (asks first)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  :add [dir]        ", Style::default().fg(Color::Magenta)),
+            Span::raw("- cargo add selected crate to a project (asks first)"),
         ]),
-        // Line::from(vec![
-        //     Span::styled("  :try              ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
-        //     Span::raw("- Try selected crate in temp directory"),
-        // ]),
         // Line::from(""),
         // Line::from(Span::styled(
         //     "🧪 Try Mode:",
@@ -952,477 +3840,2747 @@ fn render_help(f: &mut Frame, area: Rect) {
         // ]),
     ];
 
-    let paragraph = Paragraph::new(help_text)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Cyan))
-                .title(vec![
-                    Span::styled(" ❓ ", Style::default().fg(Color::Yellow)),
-                    Span::styled(
-                        "Help",
-                        Style::default()
-                            .fg(Color::Cyan)
-                            .add_modifier(Modifier::BOLD),
-                    ),
-                    Span::styled(
-                        " (Press ? or TAB to close) ",
-                        Style::default().fg(Color::DarkGray),
-                    ),
-                ])
-                .style(Style::default()),
-        )
-        .wrap(Wrap { trim: false });
+    let paragraph = Paragraph::new(help_text)
+        .block(
+            app.theme
+                .block()
+                .title(vec![
+                    Span::styled(
+                        format!(" {} ", icons.help),
+                        Style::default().fg(Color::Yellow),
+                    ),
+                    Span::styled(
+                        "Help",
+                        Style::default()
+                            .fg(Color::Cyan)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(
+                        " (Press ? or TAB to close) ",
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                ])
+                .style(Style::default()),
+        )
+        .wrap(Wrap { trim: false });
+
+    f.render_widget(paragraph, area);
+}
+
+fn render_categories(f: &mut Frame, app: &mut App, area: Rect) {
+    let icons = app.icons;
+    let items: Vec<ListItem> = app
+        .categories
+        .iter()
+        .map(|(category, count)| {
+            let label = app.category_label(category.as_deref()).to_string();
+            let name = match category {
+                Some(_) => Span::styled(label, app.theme.text()),
+                None => Span::styled(label, Style::default().fg(Color::DarkGray)),
+            };
+            ListItem::new(Line::from(vec![
+                name,
+                Span::styled(format!(" ({})", count), Style::default().fg(Color::Green)),
+            ]))
+        })
+        .collect();
+
+    let title = Span::styled(
+        format!(
+            " {} Categories ({}) ",
+            icons.categories,
+            app.categories.len()
+        ),
+        Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD),
+    );
+    let list = List::new(items)
+        .block(app.theme.block().title(title))
+        .highlight_style(app.theme.highlight())
+        .highlight_symbol(app.theme.highlight_symbol(icons.selected));
+
+    f.render_stateful_widget(list, area, &mut app.category_state);
+}
+
+/// How a crate depends on ratatui, e.g. "optional dependency" or "dev-dependency"
+fn dependency_kind(dependency: &RatatuiDependency) -> &'static str {
+    match (dependency.optional, dependency.dev_dependency) {
+        (false, false) => "dependency",
+        (true, false) => "optional dependency",
+        (false, true) => "dev-dependency",
+        (true, true) => "optional dev-dependency",
+    }
+}
+
+/// shields.io badges linking to a crate's crates.io page and docs.rs documentation
+fn badge_markdown(name: &str) -> String {
+    format!(
+        "[![crates.io](https://img.shields.io/crates/v/{name}.svg)](https://crates.io/crates/{name})\n\
+         [![docs.rs](https://img.shields.io/docsrs/{name})](https://docs.rs/{name})\n"
+    )
+}
+
+/// A Markdown table of crates: linked name, downloads, ratatui requirement, description.
+fn markdown_table(crates: &[CratePackage]) -> String {
+    // Pipes would end a cell early and line breaks would end the row
+    let cell = |text: &str| {
+        text.split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .replace('|', "\\|")
+    };
+
+    let mut out =
+        String::from("| Crate | Downloads | Ratatui | Description |\n|---|---:|---|---|\n");
+    for c in crates {
+        let ratatui = c.ratatui_dependency.version.trim();
+        out.push_str(&format!(
+            "| [{name}](https://crates.io/crates/{name}) | {} | {} | {} |\n",
+            format_number(c.downloads),
+            if ratatui.is_empty() { "-" } else { ratatui },
+            cell(&c.description),
+            name = c.name,
+        ));
+    }
+    out
+}
+
+/// Compact label for a list step in the command bar's filter chips, e.g. "mindl:10.0K"
+fn chip_label(op: &ListOp) -> String {
+    match op {
+        ListOp::Search(q) => format!("search!:{}", q),
+        ListOp::Fuzzy(q) => format!("search:{}", q),
+        ListOp::Category(Some(category)) => format!("category:{}", category),
+        ListOp::Age(bucket) => format!("age:{}", bucket.name()),
+        ListOp::Top(n) => format!("top:{}", n),
+        ListOp::Recent(n) => format!("recent:{}", n),
+        ListOp::New(n) => format!("new:{}", n),
+        ListOp::NewSince { version, .. } => format!("since:{}", version),
+        ListOp::MinDownloads(n) => format!("mindl:{}", format_number(*n)),
+        ListOp::MaxDownloads(n) => format!("maxdl:{}", format_number(*n)),
+        ListOp::Compat(req) => format!("compat:{}", req),
+        ListOp::Allow(names) => format!("allow:{}", names.len()),
+        ListOp::Deny(names) => format!("deny:{}", names.len()),
+        ListOp::Sort(order) => format!("sort:{}", order.primary.key.name()),
+        other => other.label(),
+    }
+}
+
+/// A rendered line's text without styling, trailing spaces trimmed.
+fn line_text(line: &Line) -> String {
+    line.spans
+        .iter()
+        .map(|span| span.content.as_ref())
+        .collect::<String>()
+        .trim_end()
+        .to_string()
+}
+
+/// Core and community shares of the dataset, in whole percent
+fn core_share(app: &App) -> (usize, usize) {
+    let core_pct = (app.metadata.core_libraries * 100)
+        .checked_div(app.all_crates.len())
+        .unwrap_or(0)
+        .min(100);
+    (core_pct, 100 - core_pct)
+}
+
+/// Section heading shared by the stats report and the titles of its charts
+fn stats_heading(text: String) -> Line<'static> {
+    Line::from(Span::styled(
+        text,
+        Style::default()
+            .fg(Color::Green)
+            .add_modifier(Modifier::BOLD),
+    ))
+}
+
+/// The core/community split as text bars, for `:copyview`; the stats view draws
+/// `distribution_chart` instead.
+fn distribution_lines(app: &App) -> Vec<Line<'static>> {
+    let icons = app.icons;
+    let (core_pct, community_pct) = core_share(app);
+    vec![
+        stats_heading(format!("{} Distribution:", icons.stats)),
+        Line::from(format!(
+            "  Core:      [{}] {}%",
+            icons.bar.repeat(core_pct / 2),
+            core_pct
+        )),
+        Line::from(format!(
+            "  Community: [{}] {}%",
+            icons.bar.repeat(community_pct / 2),
+            community_pct
+        )),
+        Line::from(""),
+    ]
+}
+
+/// The five most downloaded crates as text, for `:copyview`; the stats view draws
+/// `top_downloads_chart` instead.
+fn top_downloads_lines(app: &App) -> Vec<Line<'static>> {
+    let now = chrono::Utc::now();
+    let mut lines = vec![stats_heading(format!(
+        "{} Top 5 Most Downloaded:",
+        app.icons.trophy
+    ))];
+    lines.extend(top_downloads(app).into_iter().enumerate().map(|(i, c)| {
+        let [medal, name, age] = top_downloads_row(app.icons, i, c, now);
+        Line::from(vec![
+            Span::raw("  "),
+            medal,
+            name,
+            Span::raw(" "),
+            Span::styled(
+                format!("{:>10}", format_number(c.downloads)),
+                Style::default().fg(Color::Green),
+            ),
+            age,
+        ])
+    }));
+    lines
+}
+
+/// The five most downloaded crates, most downloaded first
+fn top_downloads(app: &App) -> Vec<&CratePackage> {
+    let mut top: Vec<&CratePackage> = app.all_crates.iter().collect();
+    top.sort_by_key(|c| std::cmp::Reverse(c.downloads));
+    top.truncate(5);
+    top
+}
+
+/// Medal, name and last-update age of the `rank`th most downloaded crate; the age is dimmed
+/// once the crate looks unmaintained
+fn top_downloads_row(
+    icons: &Icons,
+    rank: usize,
+    crate_pkg: &CratePackage,
+    now: chrono::DateTime<chrono::Utc>,
+) -> [Span<'static>; 3] {
+    let medal = icons.medals.get(rank).copied().unwrap_or("  ");
+    let updated_days = filter::parse_date(&crate_pkg.updated_at).map(|d| (now - d).num_days());
+    let updated_style = match updated_days {
+        Some(days) if days < STALE_AFTER_DAYS => Style::default().fg(Color::Blue),
+        _ => Style::default()
+            .fg(Color::DarkGray)
+            .add_modifier(Modifier::DIM),
+    };
+    [
+        Span::raw(format!("{} ", medal)),
+        Span::styled(
+            format!("{:20}", crate_pkg.name),
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            format!(
+                "  {:>8}",
+                updated_days.map_or_else(|| "?".to_string(), format_age)
+            ),
+            updated_style,
+        ),
+    ]
+}
+
+/// Bar symbols for the stats charts in the active icon set. Horizontal bars are drawn in
+/// whole cells, so only `full` and `empty` show up.
+fn chart_bar_set(icons: &Icons) -> symbols::bar::Set {
+    symbols::bar::Set {
+        full: icons.bar,
+        seven_eighths: icons.bar,
+        three_quarters: icons.bar,
+        five_eighths: icons.bar,
+        half: " ",
+        three_eighths: " ",
+        one_quarter: " ",
+        one_eighth: " ",
+        empty: " ",
+    }
+}
+
+/// Rows taken by `distribution_chart`: heading, two bars and a blank line
+const DISTRIBUTION_CHART_HEIGHT: u16 = 4;
+/// Rows taken by `top_downloads_chart`: heading, five bars and a blank line
+const TOP_DOWNLOADS_CHART_HEIGHT: u16 = 7;
+
+/// The core/community split as horizontal bars that stretch with the pane
+fn distribution_chart(app: &App) -> BarChart<'static> {
+    let (core_pct, community_pct) = core_share(app);
+    let bar = |label: &'static str, pct: usize, color: Color| {
+        Bar::default()
+            .label(Line::from(label))
+            .value(pct as u64)
+            .text_value(format!("{}%", pct))
+            .style(Style::default().fg(color))
+            .value_style(Style::default().fg(Color::Black).bg(color))
+    };
+    BarChart::default()
+        .block(Block::new().title(stats_heading(format!("{} Distribution:", app.icons.stats))))
+        .direction(Direction::Horizontal)
+        .bar_set(chart_bar_set(app.icons))
+        .bar_width(1)
+        .bar_gap(0)
+        .max(100)
+        .data(BarGroup::default().bars(&[
+            bar("Core", core_pct, Color::Yellow),
+            bar("Community", community_pct, Color::Green),
+        ]))
+}
+
+/// Download counts of the five most downloaded crates as horizontal bars, labelled with
+/// their medal, name and last-update age
+fn top_downloads_chart(app: &App) -> BarChart<'static> {
+    let now = chrono::Utc::now();
+    let bars: Vec<Bar> = top_downloads(app)
+        .into_iter()
+        .enumerate()
+        .map(|(i, c)| {
+            Bar::default()
+                .label(Line::from(top_downloads_row(app.icons, i, c, now).to_vec()))
+                .value(c.downloads)
+                .text_value(format_number(c.downloads))
+                .style(Style::default().fg(Color::Cyan))
+                .value_style(Style::default().fg(Color::Black).bg(Color::Cyan))
+        })
+        .collect();
+    BarChart::default()
+        .block(Block::new().title(stats_heading(format!(
+            "{} Top 5 Most Downloaded:",
+            app.icons.trophy
+        ))))
+        .direction(Direction::Horizontal)
+        .bar_set(chart_bar_set(app.icons))
+        .bar_width(1)
+        .bar_gap(0)
+        .data(BarGroup::default().bars(&bars))
+}
+
+/// The stats report as banner, left column (overview, downloads) and right column (age).
+/// Shared by the stats view and `:copyview`, which add the distribution and top 5 as
+/// charts and as text respectively.
+fn stats_sections(app: &App) -> [Vec<Line<'static>>; 3] {
+    let icons = app.icons;
+
+    // Calculate statistics
+    let total = app.all_crates.len();
+    let core = app.metadata.core_libraries;
+    let community = app.metadata.community_packages;
+
+    let total_downloads: u64 = app.all_crates.iter().map(|c| c.downloads).sum();
+    let avg_downloads = if total > 0 {
+        total_downloads / total as u64
+    } else {
+        0
+    };
+
+    let total_weekly: u64 = app.all_crates.iter().map(|c| c.recent_downloads).sum();
+
+    let mut header = vec![];
+    let mut left = vec![];
+    let mut right = vec![];
+
+    // Banner
+    header.push(Line::from(""));
+    header.push(Line::from(Span::styled(
+        "╔═══════════════════════════════════════════════════════╗",
+        Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD),
+    )));
+    header.push(banner_row(vec![Span::styled(
+        format!("{} RATATUI ECOSYSTEM STATISTICS", icons.stats),
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD),
+    )]));
+    header.push(Line::from(Span::styled(
+        "╚═══════════════════════════════════════════════════════╝",
+        Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD),
+    )));
+    header.push(Line::from(""));
+
+    // Overview
+    left.push(Line::from(Span::styled(
+        format!("{} Overview:", icons.crates),
+        Style::default()
+            .fg(Color::Green)
+            .add_modifier(Modifier::BOLD),
+    )));
+    left.push(Line::from(vec![
+        Span::raw("  Total Packages:     "),
+        Span::styled(
+            format!("{}", total),
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        ),
+    ]));
+    left.push(Line::from(vec![
+        Span::raw(format!("  {} Core Libraries:  ", icons.core)),
+        Span::styled(
+            format!("{}", core),
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ),
+    ]));
+    left.push(Line::from(vec![
+        Span::raw(format!("  {} Community:       ", icons.community)),
+        Span::styled(
+            format!("{}", community),
+            Style::default()
+                .fg(Color::Green)
+                .add_modifier(Modifier::BOLD),
+        ),
+    ]));
+    left.push(Line::from(""));
+
+    // Download stats
+    left.push(Line::from(Span::styled(
+        format!("{} Download Statistics:", icons.weekly),
+        Style::default()
+            .fg(Color::Green)
+            .add_modifier(Modifier::BOLD),
+    )));
+    left.push(Line::from(vec![
+        Span::raw("  Total Downloads:    "),
+        Span::styled(
+            format_number(total_downloads),
+            Style::default()
+                .fg(Color::Green)
+                .add_modifier(Modifier::BOLD),
+        ),
+    ]));
+    left.push(Line::from(vec![
+        Span::raw("  Average/Crate:      "),
+        Span::styled(
+            format_number(avg_downloads),
+            Style::default()
+                .fg(Color::Blue)
+                .add_modifier(Modifier::BOLD),
+        ),
+    ]));
+    left.push(Line::from(vec![
+        Span::raw("  Weekly Downloads:   "),
+        Span::styled(
+            format_number(total_weekly),
+            Style::default()
+                .fg(Color::Magenta)
+                .add_modifier(Modifier::BOLD),
+        ),
+    ]));
+
+    // Age buckets by creation date
+    right.push(Line::from(Span::styled(
+        format!("{} Age (by creation date):", icons.stats),
+        Style::default()
+            .fg(Color::Green)
+            .add_modifier(Modifier::BOLD),
+    )));
+    let now = chrono::Utc::now();
+    let mut bucket_counts = [0usize; AgeBucket::ALL.len()];
+    let mut undated = 0;
+    for c in &app.all_crates {
+        match AgeBucket::of(&c.created_at, now) {
+            Some(bucket) => bucket_counts[bucket as usize] += 1,
+            None => undated += 1,
+        }
+    }
+    for (bucket, count) in AgeBucket::ALL.iter().zip(bucket_counts) {
+        right.push(Line::from(vec![
+            Span::raw(format!("  {:<8}{:<20}", bucket.name(), bucket.range())),
+            Span::styled(
+                count.to_string(),
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ]));
+    }
+    if undated > 0 {
+        right.push(Line::from(vec![
+            Span::raw(format!("  {:<28}", "unknown date")),
+            Span::styled(undated.to_string(), Style::default().fg(Color::DarkGray)),
+        ]));
+    }
+    right.push(Line::from(""));
+
+    [header, left, right]
+}
+
+fn render_stats(f: &mut Frame, app: &App, area: Rect) {
+    let icons = app.icons;
+    let [header, left, right] = stats_sections(app);
+
+    let footer = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            format!("{} Tip: Press TAB to go back to list view", icons.tip),
+            Style::default().fg(Color::DarkGray),
+        )),
+    ];
+
+    let block = app
+        .theme
+        .block()
+        .title(vec![
+            Span::styled(
+                format!(" {} ", icons.stats),
+                Style::default().fg(Color::Yellow),
+            ),
+            Span::styled(
+                "Statistics",
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ])
+        .style(Style::default());
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let column = |lines: Vec<Line<'static>>| Paragraph::new(lines).wrap(Wrap { trim: false });
+    let charts_height = DISTRIBUTION_CHART_HEIGHT + TOP_DOWNLOADS_CHART_HEIGHT;
+    // Charts on top, the text sections below them
+    let render_right = |f: &mut Frame, lines: Vec<Line<'static>>, area: Rect| {
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(DISTRIBUTION_CHART_HEIGHT),
+                Constraint::Length(TOP_DOWNLOADS_CHART_HEIGHT),
+                Constraint::Min(0),
+            ])
+            .split(area);
+        f.render_widget(distribution_chart(app), rows[0]);
+        f.render_widget(top_downloads_chart(app), rows[1]);
+        f.render_widget(column(lines), rows[2]);
+    };
+
+    // Wide panes get overview/downloads and charts/age/top 5 side by side
+    if inner.width >= STATS_TWO_COLUMN_MIN_WIDTH {
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(header.len() as u16),
+                Constraint::Length(left.len().max(right.len() + charts_height as usize) as u16),
+                Constraint::Length(footer.len() as u16),
+                Constraint::Min(0),
+            ])
+            .split(inner);
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Length(STATS_LEFT_COLUMN_WIDTH),
+                Constraint::Min(0),
+            ])
+            .split(rows[1]);
+
+        f.render_widget(column(header), rows[0]);
+        f.render_widget(column(left), columns[0]);
+        render_right(f, right, columns[1]);
+        f.render_widget(column(footer), rows[2]);
+    } else {
+        let mut lines = header;
+        lines.extend(left);
+        lines.push(Line::from(""));
+        // Rows once wrapped, so the charts start below the text however narrow the pane
+        let height: usize = lines
+            .iter()
+            .map(|line| line.width().div_ceil(inner.width.max(1) as usize).max(1))
+            .sum();
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(height as u16), Constraint::Min(0)])
+            .split(inner);
+        let mut right = right;
+        right.extend(footer);
+        f.render_widget(column(lines), rows[0]);
+        render_right(f, right, rows[1]);
+    }
+}
+
+fn render_log(f: &mut Frame, app: &App, area: Rect) {
+    let lines: Vec<Line> = app
+        .status_log
+        .iter()
+        .map(|(timestamp, message)| {
+            Line::from(vec![
+                Span::styled(
+                    format!("[{}] ", timestamp),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Span::styled(message.as_str(), app.theme.text()),
+            ])
+        })
+        .collect();
+
+    // Keep the newest entry at the bottom unless the user has scrolled up
+    let visible = area.height.saturating_sub(2) as usize;
+    let top = lines
+        .len()
+        .saturating_sub(visible)
+        .saturating_sub(app.log_scroll);
+
+    let paragraph = Paragraph::new(lines)
+        .block(app.theme.block().title(vec![
+                    Span::styled(
+                        format!(" {} Log ", app.icons.log),
+                        Style::default()
+                            .fg(Color::Cyan)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(
+                        "(PgUp/PgDn to scroll, L to close) ",
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                ]))
+        .scroll((top as u16, 0));
+
+    f.render_widget(paragraph, area);
+}
+
+fn render_command_bar(f: &mut Frame, app: &App, area: Rect) {
+    let text = match app.mode {
+        Mode::Normal => Text::from(Line::from(vec![
+            Span::styled(
+                " NORMAL ",
+                Style::default()
+                    .bg(Color::Blue)
+                    .fg(Color::Black)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(" "),
+            Span::styled(&app.status_message, app.theme.text()),
+        ])),
+        Mode::Command => Text::from(Line::from(vec![
+            Span::styled(
+                " COMMAND ",
+                Style::default()
+                    .bg(Color::Green)
+                    .fg(Color::Black)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                " :",
+                Style::default()
+                    .fg(Color::Green)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(&app.command_input, Style::default().fg(Color::Yellow)),
+            Span::styled(
+                "_",
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::SLOW_BLINK),
+            ),
+            Span::styled(
+                app.search_preview
+                    .map(|n| format!("  ({} matches)", n))
+                    .unwrap_or_default(),
+                Style::default().fg(Color::DarkGray),
+            ),
+            Span::styled(
+                app.completion
+                    .as_ref()
+                    .map(|(_, candidates, _)| format!("  [{}]", candidates.join(" ")))
+                    .unwrap_or_default(),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ])),
+        Mode::Search => Text::from(Line::from(vec![
+            Span::styled(
+                " SEARCH ",
+                Style::default()
+                    .bg(Color::Cyan)
+                    .fg(Color::Black)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                " /",
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(&app.command_input, Style::default().fg(Color::Yellow)),
+            Span::styled(
+                "_",
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::SLOW_BLINK),
+            ),
+            Span::styled(
+                format!("  ({} matches)", app.filtered_crates.len()),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ])),
+        Mode::Jump => Text::from(Line::from(vec![
+            Span::styled(
+                " JUMP ",
+                Style::default()
+                    .bg(Color::Magenta)
+                    .fg(Color::Black)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(" "),
+            Span::styled(&app.status_message, Style::default().fg(Color::Magenta)),
+        ])),
+        Mode::Confirm => Text::from(Line::from(vec![
+            Span::styled(
+                " CONFIRM ",
+                Style::default()
+                    .bg(Color::Yellow)
+                    .fg(Color::Black)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(" "),
+            Span::styled(&app.status_message, Style::default().fg(Color::Yellow)),
+        ])),
+    };
+
+    // How much the current filter weighs, then one busy indicator for all background work
+    let mut block = app.theme.block();
+    if app.is_filtered() {
+        block = block.title(Span::styled(
+            format!(
+                " filtered total: {} downloads ",
+                format_number(app.filtered_downloads)
+            ),
+            Style::default().fg(Color::Green),
+        ));
+    }
+    if app.busy > 0 {
+        block = block.title(
+            Line::from(Span::styled(
+                format!(" {} working ", app.spinner()),
+                Style::default().fg(Color::Yellow),
+            ))
+            .right_aligned(),
+        );
+    }
+    // Each step of the current list as a numbered chip; the digit removes it
+    if app.list_ops.iter().any(|op| *op != ListOp::All) {
+        let mut chips = vec![Span::raw(" ")];
+        for (i, op) in app.list_ops.iter().enumerate() {
+            if *op == ListOp::All {
+                continue;
+            }
+            chips.push(Span::styled(
+                format!(" {}:{} ", i + 1, chip_label(op)),
+                Style::default().fg(Color::Black).bg(Color::Cyan),
+            ));
+            chips.push(Span::raw(" "));
+        }
+        block = block.title_bottom(Line::from(chips));
+    }
+    // Where the data came from and how old it is, for as long as the app runs
+    if let Some(provenance) = app.provenance {
+        let (label, color) = match provenance.origin {
+            DataOrigin::Cache if app.offline => ("offline — cache may be stale", Color::DarkGray),
+            DataOrigin::Cache => ("cache", Color::DarkGray),
+            DataOrigin::Download => ("fresh", Color::Green),
+        };
+        block = block.title_bottom(
+            Line::from(Span::styled(
+                format!(" {} · {} old ", label, format_duration(provenance.age())),
+                Style::default().fg(color),
+            ))
+            .right_aligned(),
+        );
+    }
+    let paragraph = Paragraph::new(text).block(block);
+
+    f.render_widget(paragraph, area);
+}
+
+// ============================================================================
+// Event Handling
+// ============================================================================
+
+fn handle_events(app: &mut App) -> Result<bool> {
+    // Input wakes `poll` immediately, so a long idle timeout only saves redraws, not latency
+    let timeout = if app.busy > 0 {
+        app.poll_interval
+            .min(Duration::from_millis(ANIMATION_POLL_MS))
+    } else {
+        app.poll_interval
+    };
+    if !event::poll(timeout)? {
+        return Ok(false);
+    }
+    let Event::Key(key) = event::read()? else {
+        return Ok(false);
+    };
+
+    // Holding j/k can queue more repeats than we draw frames. Drop the buffered copies so the
+    // selection moves one step per frame and stops as soon as the key is released.
+    if app.mode == Mode::Normal && app.popup.is_none() && is_repeatable_navigation(&key) {
+        while event::poll(Duration::ZERO)? {
+            match event::read()? {
+                Event::Key(next) if next == key => {}
+                Event::Key(next) => {
+                    if handle_key(app, key) {
+                        return Ok(true);
+                    }
+                    return Ok(handle_key(app, next));
+                }
+                _ => {}
+            }
+        }
+    }
+    Ok(handle_key(app, key))
+}
+
+/// Navigation keys whose buffered repeats are coalesced in `handle_events`.
+fn is_repeatable_navigation(key: &KeyEvent) -> bool {
+    match key.code {
+        KeyCode::Char('j') | KeyCode::Char('k') | KeyCode::Down | KeyCode::Up => true,
+        KeyCode::Char('d') | KeyCode::Char('u') => key.modifiers.contains(KeyModifiers::CONTROL),
+        _ => false,
+    }
+}
+
+/// Apply one key press. Returns true when the app should quit.
+fn handle_key(app: &mut App, key: KeyEvent) -> bool {
+    // Only repeated Tab cycles completions; any other key keeps what was filled in
+    if key.code != KeyCode::Tab {
+        app.completion = None;
+    }
+    // Any other key dismisses an open popup; pickers also take j/k, Enter and digits
+    if let Some(mut popup) = app.popup.take() {
+        if let Some(top) = popup.scroll {
+            let last = popup.lines.len().saturating_sub(1);
+            popup.scroll = match key.code {
+                KeyCode::Esc | KeyCode::Char('q') => return false,
+                KeyCode::Char('j') | KeyCode::Down => Some((top + 1).min(last)),
+                KeyCode::Char('k') | KeyCode::Up => Some(top.saturating_sub(1)),
+                KeyCode::PageDown => Some((top + 10).min(last)),
+                KeyCode::PageUp => Some(top.saturating_sub(10)),
+                KeyCode::Char('g') => Some(0),
+                KeyCode::Char('G') => Some(last),
+                _ => Some(top),
+            };
+            app.popup = Some(popup);
+            return false;
+        }
+        let len = popup.picks.len();
+        let chosen = match key.code {
+            _ if len == 0 => None,
+            KeyCode::Char('j') | KeyCode::Down => {
+                popup.selected = (popup.selected + 1) % len;
+                app.popup = Some(popup);
+                return false;
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                popup.selected = popup.selected.checked_sub(1).unwrap_or(len - 1);
+                app.popup = Some(popup);
+                return false;
+            }
+            KeyCode::Enter => popup.picks.get(popup.selected),
+            KeyCode::Char(c) => c
+                .to_digit(10)
+                .and_then(|d| (d as usize).checked_sub(1))
+                .and_then(|i| popup.picks.get(i)),
+            _ => None,
+        };
+        if let Some(pick) = chosen.cloned() {
+            app.activate_pick(pick);
+        }
+        return false;
+    }
+
+    if app.mode == Mode::Normal && app.view == View::Categories && app.handle_category_key(key.code)
+    {
+        return false;
+    }
+
+    // Refresh deltas last until the user moves on
+    if app.mode == Mode::Normal
+        && (is_repeatable_navigation(&key) || matches!(key.code, KeyCode::Char('g' | 'G')))
+    {
+        app.download_deltas.clear();
+    }
+
+    match app.mode {
+        Mode::Normal => match key.code {
+            // Quit
+            KeyCode::Char('q') => return true,
+
+            // Navigation
+            KeyCode::Char('j') | KeyCode::Down => app.next(),
+            KeyCode::Char('k') | KeyCode::Up => app.previous(),
+            KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => app.next_page(),
+            KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                app.previous_page()
+            }
+            KeyCode::Char('g') => app.reset_selection(),
+            KeyCode::Char('G') => app
+                .list_state
+                .select(app.filtered_crates.len().checked_sub(1)),
+
+            // Status log
+            KeyCode::Char('L') => app.toggle_log(),
+
+            // Sort by name and jump to a letter
+            KeyCode::Char('\'') => app.start_letter_jump(),
+            KeyCode::Char('r') => app.reverse_list(),
+            KeyCode::Char('*') => app.jump_to_search_match(),
+
+            // Filters
+            KeyCode::Char('i') => app.invert_filter(),
+            KeyCode::Backspace => app.previous_list(),
+            KeyCode::Char('F') => app.toggle_favorites_only(),
+
+            // Favorites
+            KeyCode::Char('f') => app.toggle_favorite(),
+
+            // List density
+            KeyCode::Char('%') => app.count_as_percent = !app.count_as_percent,
+            KeyCode::Char('x') => app.copy_view(),
+            KeyCode::Char(c @ '1'..='9') => app.remove_list_op(c as usize - '0' as usize),
+            KeyCode::Char('b') => app.copy_badges(),
+            KeyCode::Char('y') => app.copy_install(false),
+            KeyCode::Char('Y') => app.copy_install(true),
+            KeyCode::Char(']') => app.jump_to_favorite(true),
+            KeyCode::Char('[') => app.jump_to_favorite(false),
+            KeyCode::Char('m') => {
+                app.gutter = !app.gutter;
+                app.set_status(if app.gutter {
+                    "Marker gutter shown"
+                } else {
+                    "Marker gutter hidden"
+                });
+            }
+            KeyCode::Char('c') => {
+                app.compact_list = !app.compact_list;
+                app.set_status(if app.compact_list {
+                    "Compact list: one line per crate"
+                } else {
+                    "Expanded list: two lines per crate"
+                });
+            }
+            KeyCode::PageUp if app.show_log => app.scroll_log_up(5),
+            KeyCode::PageDown if app.show_log => app.scroll_log_down(5),
+
+            // Views
+            KeyCode::Tab => {
+                app.view = match app.view {
+                    View::List => View::Stats,
+                    View::Stats | View::Help | View::Categories => View::List,
+                };
+            }
+            KeyCode::Char('C') => app.toggle_categories(),
+            KeyCode::Char('o') => app.open_link(LinkKind::Repository),
+            KeyCode::Char('O') => app.open_link(LinkKind::Documentation),
+            KeyCode::Char('H') => app.open_link(LinkKind::Homepage),
+            KeyCode::Char('A') => app.open_all_links(),
+            KeyCode::Char('v') => app.show_recently_viewed(),
+            KeyCode::Char('t') => app.show_crate_categories(),
+            KeyCode::Char('e') => app.set_full_description(!app.full_description),
+            KeyCode::Char('?') => {
+                app.view = if app.view == View::Help {
+                    View::List
+                } else {
+                    View::Help
+                };
+            }
+
+            // Commands
+            KeyCode::Char(':') => {
+                app.mode = Mode::Command;
+                app.command_input.clear();
+                app.history_cursor = app.command_history.len();
+            }
+            KeyCode::Char('/') => app.start_live_search(),
+
+            _ => {}
+        },
+        Mode::Command => match key.code {
+            KeyCode::Tab => app.complete_command(),
+            KeyCode::Enter => {
+                app.record_command();
+                if app.command_input == "q" || app.command_input == "quit" {
+                    return true;
+                }
+                // `:search ` then Enter with nothing typed: leave quietly and keep the list
+                if app.command_input.starts_with("search ") && app.command_input.trim() == "search"
+                {
+                    app.mode = Mode::Normal;
+                    app.command_input.clear();
+                    app.search_preview = None;
+                    return false;
+                }
+                app.execute_command();
+            }
+            KeyCode::Char(c) => {
+                app.command_input.push(c);
+                app.update_search_preview();
+            }
+            KeyCode::Backspace => {
+                app.command_input.pop();
+                app.update_search_preview();
+            }
+            KeyCode::Up => app.recall_command(true),
+            KeyCode::Down => app.recall_command(false),
+            KeyCode::Esc => {
+                app.mode = Mode::Normal;
+                app.command_input.clear();
+                app.search_preview = None;
+            }
+            _ => {}
+        },
+        Mode::Search => match key.code {
+            KeyCode::Enter => app.commit_live_search(),
+            KeyCode::Esc => app.cancel_live_search(),
+            KeyCode::Char(c) => {
+                app.command_input.push(c);
+                app.update_live_search();
+            }
+            KeyCode::Backspace => {
+                app.command_input.pop();
+                app.update_live_search();
+            }
+            KeyCode::Down => app.next(),
+            KeyCode::Up => app.previous(),
+            _ => {}
+        },
+        Mode::Jump => match key.code {
+            KeyCode::Char(c) if c.is_alphanumeric() => app.jump_to_letter(c),
+            KeyCode::Esc => app.cancel_letter_jump(),
+            _ => {}
+        },
+        Mode::Confirm => match key.code {
+            KeyCode::Char('y') | KeyCode::Char('Y') => app.confirm_pending(),
+            KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => app.cancel_pending(),
+            _ => {}
+        },
+    }
+    false
+}
+
+// ============================================================================
+// Main
+// ============================================================================
+
+fn main() -> Result<()> {
+    let args = cli::Args::parse()?;
+    if args.help {
+        println!("{}", cli::USAGE);
+        return Ok(());
+    }
+    cache::set_profile(args.profile)?;
+    cache::set_remote_url(args.url)?;
+    if let Err(e) = logging::init() {
+        eprintln!("⚠ {:#}; continuing without a log file", e);
+    }
+
+    let mut config = config::load()?;
+    if let Some(name) = &args.theme {
+        config.theme = theme::builtin(name)?;
+    }
+    let theme = Theme::from_config(&config.theme)?;
+    if args.dump_config {
+        print!("{}", dump_config(&config)?);
+        return Ok(());
+    }
+
+    // Raw mode and the alternate screen garble pipes and dumb terminals; print instead
+    let interactive = is_interactive(
+        std::env::var("TERM").ok().as_deref(),
+        io::stdin().is_terminal() && io::stdout().is_terminal(),
+    );
+    if args.print_search || !interactive {
+        if !args.print_search {
+            eprintln!(
+                "Not an interactive terminal; printing crate names instead of starting the TUI"
+            );
+        }
+        let (data, _) = if args.offline {
+            cache::get_data_offline()?
+        } else {
+            get_data(false)?
+        };
+        if !print_search(
+            &data.crates,
+            args.search.as_deref(),
+            args.json,
+            args.downloads,
+        )? {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    // Cache directory warnings print once, before the TUI owns the terminal; later progress
+    // notes would only garble it
+    let _ = cache::get_cache_dir();
+    cache::set_quiet(true);
+
+    // Setup terminal
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    let backend = CrosstermBackend::new(stdout);
+    let mut terminal = Terminal::new(backend)?;
+
+    // Load data behind a spinner, then create and run the app
+    let icons = icons::detect();
+    let mut app = None;
+    let result = load_data(
+        &mut terminal,
+        icons,
+        &theme,
+        args.offline,
+        config.cache.offline_first,
+    )
+        .and_then(|loaded| {
+            let Some((data, provenance, stale)) = loaded else {
+                return Ok(());
+            };
+            let app = app.insert(App::new(
+                data,
+                icons,
+                theme,
+                &config,
+                &Session::load(),
+                Favorites::load(),
+                args.search.as_deref(),
+            ));
+            app.provenance = Some(provenance);
+            app.offline = args.offline;
+            if !cache::is_persistent() {
+                app.persistent = false;
+                app.set_status(format!(
+                    "{} Cache directory is not writable; favorites and session are kept for this session only",
+                    app.icons.warning
+                ));
+            }
+            if stale {
+                app.start_refresh();
+            }
+            run_app(&mut terminal, app)
+        });
+
+    // Restore terminal
+    disable_raw_mode()?;
+    execute!(
+        terminal.backend_mut(),
+        LeaveAlternateScreen,
+        DisableMouseCapture
+    )?;
+    terminal.show_cursor()?;
+
+    if let Some(app) = app
+        && let Err(e) = app.to_session().save()
+    {
+        eprintln!("Warning: {:#}", e);
+    }
+
+    result
+}
+
+/// Load the dataset on a background thread, animating a spinner until it arrives. When
+/// loading fails the error is shown with a retry prompt.
+///
+/// Returns the data, where it came from and whether it is stale, or `None` if the user quit
+/// while waiting.
+///
+/// # Errors
+/// Returns the load error if the user quits from the retry prompt.
+fn load_data(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    icons: &'static Icons,
+    theme: &Theme,
+    offline: bool,
+    offline_first: bool,
+) -> Result<Option<(CratesData, Provenance, bool)>> {
+    let mut tick = 0;
+    loop {
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let loaded = if offline {
+                cache::get_data_offline().map(|(data, provenance)| (data, provenance, false))
+            } else if offline_first {
+                cache::get_data_offline_first()
+            } else {
+                get_data(false).map(|(data, provenance)| (data, provenance, false))
+            };
+            let _ = tx.send(loaded);
+        });
+
+        let error = loop {
+            match rx.try_recv() {
+                Ok(Ok(loaded)) => return Ok(Some(loaded)),
+                Ok(Err(e)) => break e,
+                Err(TryRecvError::Empty) => {}
+                Err(TryRecvError::Disconnected) => {
+                    break anyhow::anyhow!("Loading stopped unexpectedly");
+                }
+            }
+            terminal.draw(|f| {
+                render_loading(f, icons, theme, tick, cache::download_progress(), None)
+            })?;
+            if event::poll(Duration::from_millis(ANIMATION_POLL_MS))?
+                && let Event::Key(key) = event::read()?
+                && is_quit_key(&key)
+            {
+                return Ok(None);
+            }
+            tick = tick.wrapping_add(1);
+        };
+
+        log::warn!("startup load failed: {:#}", error);
+        let message = format!("{:#}", error);
+        loop {
+            terminal.draw(|f| render_loading(f, icons, theme, tick, None, Some(&message)))?;
+            if let Event::Key(key) = event::read()? {
+                if key.code == KeyCode::Char('r') {
+                    break;
+                }
+                if is_quit_key(&key) {
+                    return Err(error);
+                }
+            }
+        }
+    }
+}
+
+/// q, Esc or Ctrl-C
+fn is_quit_key(key: &KeyEvent) -> bool {
+    matches!(key.code, KeyCode::Char('q') | KeyCode::Esc)
+        || (key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL))
+}
+
+/// The startup screen: a spinner while the dataset loads, or the error and a retry prompt.
+///
+/// `progress` is the running download's bytes received and expected size: a known size
+/// fills a gauge, an unknown one keeps the spinner with a byte count.
+fn render_loading(
+    f: &mut Frame,
+    icons: &Icons,
+    theme: &Theme,
+    tick: usize,
+    progress: Option<(u64, Option<u64>)>,
+    error: Option<&str>,
+) {
+    let title = match cache::active_profile() {
+        Some(profile) => format!(" {} Ratcrate (profile '{}') ", icons.logo, profile),
+        None => format!(" {} Ratcrate ", icons.logo),
+    };
+    let spinner = icons.spinner[tick % icons.spinner.len()];
+    let gauge = match progress {
+        Some((received, Some(total))) if error.is_none() && total > 0 => Some(
+            Gauge::default()
+                .gauge_style(Style::default().fg(Color::Cyan))
+                .ratio((received as f64 / total as f64).min(1.0))
+                .label(format!(
+                    "{} of {}",
+                    format_bytes(received),
+                    format_bytes(total)
+                )),
+        ),
+        _ => None,
+    };
+    let status = match progress {
+        None => format!("{} Loading Ratcrate data...", spinner),
+        Some(_) if gauge.is_some() => "Downloading Ratcrate data...".to_string(),
+        Some((received, _)) => format!(
+            "{} Downloading Ratcrate data... {}",
+            spinner,
+            format_bytes(received)
+        ),
+    };
+    let lines = match error {
+        None => vec![
+            Line::from(""),
+            Line::from(Span::styled(status, Style::default().fg(Color::Cyan))),
+            Line::from(""),
+            Line::from(Span::styled(
+                "q to quit",
+                Style::default().fg(Color::DarkGray),
+            )),
+        ],
+        Some(error) => vec![
+            Line::from(""),
+            Line::from(Span::styled(
+                format!("{} Could not load data", icons.error),
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            )),
+            Line::from(error.to_string()),
+            Line::from(""),
+            Line::from(Span::styled(
+                "r to retry, q to quit",
+                Style::default().fg(Color::DarkGray),
+            )),
+        ],
+    };
+    // Tall enough for the lines plus borders and a little room for a wrapped error
+    let column = centered_rect(60, 100, f.area());
+    let height = (lines.len() as u16 + 4).min(column.height);
+    let area = Rect {
+        y: column.y + (column.height - height) / 2,
+        height,
+        ..column
+    };
+    let block = theme.block().title(title);
+    let inner = block.inner(area);
+    f.render_widget(Clear, area);
+    f.render_widget(
+        Paragraph::new(lines)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true })
+            .block(block)
+            .style(theme.text()),
+        area,
+    );
+    // On the blank row under the status line
+    if let Some(gauge) = gauge
+        && inner.height > 2
+    {
+        let row = Rect {
+            x: inner.x + 2,
+            y: inner.y + 2,
+            width: inner.width.saturating_sub(4),
+            height: 1,
+        };
+        f.render_widget(gauge, row);
+    }
+}
+
+/// Whether the TUI can run: a real terminal on both ends that isn't `TERM=dumb`.
+fn is_interactive(term: Option<&str>, tty: bool) -> bool {
+    tty && term != Some("dumb")
+}
+
+/// Print the crates matching `query` (every crate when `None`) for `ratcrate-tui search` and
+/// non-interactive runs, using the same `ListOp` as `:search` so results match the TUI.
+/// Returns whether anything matched.
+fn print_search(
+    crates: &[CratePackage],
+    query: Option<&str>,
+    json: bool,
+    downloads: bool,
+) -> Result<bool> {
+    let op = match query {
+        Some(query) => ListOp::Fuzzy(query.to_lowercase()),
+        None => ListOp::All,
+    };
+    let matches = op.apply(crates, vec![]);
+
+    let text = if json {
+        serde_json::to_string_pretty(&matches)? + "\n"
+    } else {
+        matches
+            .iter()
+            .map(|c| {
+                if downloads {
+                    format!("{}\t{}\n", c.name, c.downloads)
+                } else {
+                    format!("{}\n", c.name)
+                }
+            })
+            .collect()
+    };
+    // A reader that stops early (`| head`) is not an error
+    match io::stdout().lock().write_all(text.as_bytes()) {
+        Err(e) if e.kind() != io::ErrorKind::BrokenPipe => return Err(e.into()),
+        _ => {}
+    }
+    Ok(!matches.is_empty())
+}
 
-    f.render_widget(paragraph, area);
+/// The effective settings after defaults, the config file, environment variables and flags,
+/// as TOML with the non-config inputs listed in comments.
+fn dump_config(config: &Config) -> Result<String> {
+    let config_file = match config::get_config_file() {
+        Some(path) if path.exists() => path.display().to_string(),
+        Some(path) => format!("{} (not found, using defaults)", path.display()),
+        None => "(no config directory)".to_string(),
+    };
+    let icons = if std::ptr::eq(icons::detect(), &icons::ASCII) {
+        "ascii"
+    } else {
+        "unicode"
+    };
+
+    let mut out = String::new();
+    out.push_str(&format!("# config file: {}\n", config_file));
+    out.push_str(&format!(
+        "# profile: {}\n",
+        cache::active_profile().unwrap_or("(default)")
+    ));
+    out.push_str(&format!(
+        "# cache dir: {}\n",
+        cache::get_profile_dir()?.display()
+    ));
+    out.push_str(&format!(
+        "# cache ttl: {}h\n",
+        cache::cache_max_age().as_secs() / 3600
+    ));
+    out.push_str(&format!("# data url: {}\n", cache::remote_url()));
+    out.push_str(&format!("# icons: {}\n\n", icons));
+    out.push_str(&toml::to_string_pretty(config)?);
+    Ok(out)
 }
 
-fn render_stats(f: &mut Frame, app: &App, area: Rect) {
-    // Calculate statistics
-    let total = app.all_crates.len();
-    let core = app.metadata.core_libraries;
-    let community = app.metadata.community_packages;
+fn run_app(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, app: &mut App) -> Result<()> {
+    loop {
+        app.tick = app.tick.wrapping_add(1);
+        app.poll_refresh();
+        app.poll_plan();
+        app.track_viewed(Instant::now());
 
-    let total_downloads: u64 = app.all_crates.iter().map(|c| c.downloads).sum();
-    let avg_downloads = if total > 0 {
-        total_downloads / total as u64
+        terminal.draw(|f| ui(f, app))?;
+
+        if handle_events(app)? {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// Greedily wrap `text` into lines of at most `width` display columns.
+///
+/// Words wider than `width` get a line of their own; the paragraph wrap breaks them further.
+fn wrap_words(text: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut lines = vec![];
+    let mut current = String::new();
+
+    for word in text.split_whitespace() {
+        let needed = if current.is_empty() {
+            word.width()
+        } else {
+            current.width() + 1 + word.width()
+        };
+        if needed > width && !current.is_empty() {
+            lines.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(word);
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+/// Download changes per crate id between two datasets, for crates in both that changed.
+fn download_deltas(old: &[CratePackage], new: &[CratePackage]) -> HashMap<String, i64> {
+    let before: HashMap<&str, u64> = old.iter().map(|c| (c.id.as_str(), c.downloads)).collect();
+    new.iter()
+        .filter_map(|c| {
+            let delta = c.downloads as i64 - *before.get(c.id.as_str())? as i64;
+            (delta != 0).then(|| (c.id.clone(), delta))
+        })
+        .collect()
+}
+
+/// Expand a leading `~/` to the home directory.
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+/// Cut `text` to at most `width` display columns, ending in `…` when anything was dropped.
+fn truncate_to_width(text: &str, width: usize) -> Cow<'_, str> {
+    if text.width() <= width {
+        return Cow::Borrowed(text);
+    }
+    let mut truncated = String::new();
+    let mut used = 0;
+    for c in text.chars() {
+        let w = c.width().unwrap_or(0);
+        if used + w + 1 > width {
+            break;
+        }
+        truncated.push(c);
+        used += w;
+    }
+    if width > 0 {
+        truncated.push('…');
+    }
+    Cow::Owned(truncated)
+}
+
+/// Join up to five values for a status message, noting how many were left out
+fn summarize(values: &[String]) -> String {
+    const MAX_SHOWN: usize = 5;
+    let shown = values
+        .iter()
+        .take(MAX_SHOWN)
+        .map(|v| format!("'{}'", v))
+        .collect::<Vec<_>>()
+        .join(", ");
+    if values.len() > MAX_SHOWN {
+        format!("{} (+{} more)", shown, values.len() - MAX_SHOWN)
     } else {
-        0
+        shown
+    }
+}
+
+/// Format a byte count as B / KB / MB
+fn format_bytes(n: u64) -> String {
+    if n >= 1024 * 1024 {
+        format!("{:.1} MB", n as f64 / (1024.0 * 1024.0))
+    } else if n >= 1024 {
+        format!("{:.1} KB", n as f64 / 1024.0)
+    } else {
+        format!("{} B", n)
+    }
+}
+
+/// Format a duration coarsely, e.g. "2d 3h", "4h 12m", "35s"
+fn format_duration(d: Duration) -> String {
+    let secs = d.as_secs();
+    let (days, hours, mins) = (secs / 86_400, secs % 86_400 / 3600, secs % 3600 / 60);
+    if days > 0 {
+        format!("{}d {}h", days, hours)
+    } else if hours > 0 {
+        format!("{}h {}m", hours, mins)
+    } else if mins > 0 {
+        format!("{}m {}s", mins, secs % 60)
+    } else {
+        format!("{}s", secs)
+    }
+}
+
+/// Parse a count like "100000", "50K" or "1.5M" (the inverse of `format_number`)
+fn parse_number(s: &str) -> Option<u64> {
+    let s = s.trim();
+    let (digits, multiplier) = match s.chars().last()? {
+        'k' | 'K' => (&s[..s.len() - 1], 1_000.0),
+        'm' | 'M' => (&s[..s.len() - 1], 1_000_000.0),
+        _ => (s, 1.0),
+    };
+    let value: f64 = digits.parse().ok()?;
+    if !value.is_finite() || value < 0.0 {
+        return None;
+    }
+    Some((value * multiplier).round() as u64)
+}
+
+fn format_number(n: u64) -> String {
+    if n >= 1_000_000 {
+        format!("{:.1}M", n as f64 / 1_000_000.0)
+    } else if n >= 1_000 {
+        format!("{:.1}K", n as f64 / 1_000.0)
+    } else {
+        n.to_string()
+    }
+}
+
+/// Short relative age, e.g. "5d ago", "3mo ago" or "2y ago".
+fn format_age(days: i64) -> String {
+    match days {
+        ..1 => "today".to_string(),
+        1..30 => format!("{}d ago", days),
+        30..365 => format!("{}mo ago", days / 30),
+        _ => format!("{}y ago", days / 365),
+    }
+}
+
+/// A dataset date as "3 days ago (2025-06-01)" plus its age in days. Dates that don't parse
+/// are shown as stored, or as "unknown" when missing.
+fn relative_date(raw: &str, now: chrono::DateTime<chrono::Utc>) -> (String, Option<i64>) {
+    let Some(date) = filter::parse_date(raw) else {
+        let raw = raw.trim();
+        return (
+            if raw.is_empty() { "unknown" } else { raw }.to_string(),
+            None,
+        );
+    };
+    let days = (now - date).num_days();
+    let plural =
+        |n: i64, unit: &str| format!("{} {}{} ago", n, unit, if n == 1 { "" } else { "s" });
+    let relative = match days {
+        ..1 => "today".to_string(),
+        1..30 => plural(days, "day"),
+        30..365 => plural(days / 30, "month"),
+        _ => plural(days / 365, "year"),
     };
+    (
+        format!("{} ({})", relative, date.format("%Y-%m-%d")),
+        Some(days),
+    )
+}
+
+// ============================================================================
+// Note: cache.rs and types.rs are EXACTLY the same as ratcrate-cli
+// Just copy them from the CLI project!
+// ============================================================================
+
+// ---------------------------------------------------------------------------
+// Unit tests for main.rs
+// ---------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn package(id: usize, name: &str) -> CratePackage {
+        CratePackage {
+            id: id.to_string(),
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn test_app(names: &[&str]) -> App {
+        app_with(
+            names
+                .iter()
+                .enumerate()
+                .map(|(i, name)| package(i, name))
+                .collect(),
+        )
+    }
+
+    fn app_with(crates: Vec<CratePackage>) -> App {
+        App::new(
+            CratesData::from_crates(crates),
+            &icons::ASCII,
+            Theme::default(),
+            &Config::default(),
+            &Session::default(),
+            Favorites::default(),
+            None,
+        )
+    }
+
+    fn press(app: &mut App, code: KeyCode) -> bool {
+        handle_key(app, KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    #[test]
+    fn test_new_dataset_version_pops_up_once() {
+        let mut data = CratesData::from_crates(vec![package(0, "ratatui"), package(1, "gitui")]);
+        data.metadata.version = "1.1.0".to_string();
+        let session = Session {
+            data_version: Some("1.0.0".to_string()),
+            crate_count: Some(1),
+            ..Default::default()
+        };
+        let new_app = |session: &Session| {
+            App::new(
+                data.clone(),
+                &icons::ASCII,
+                Theme::default(),
+                &Config::default(),
+                session,
+                Favorites::default(),
+                None,
+            )
+        };
+
+        let app = new_app(&session);
+        let popup = app
+            .popup
+            .as_ref()
+            .expect("version change should show a popup");
+        assert_eq!(popup.title, "What's New");
+        assert!(app.status_message.contains("v1.1.0"));
+
+        assert!(new_app(&app.to_session()).popup.is_none());
+        assert!(new_app(&Session::default()).popup.is_none());
+    }
+
+    #[test]
+    fn test_slash_then_enter_is_a_quiet_no_op() {
+        let mut app = test_app(&["ratatui", "tui-input", "gitui"]);
+        app.command_input = "search input".to_string();
+        app.execute_command();
+        let status = app.status_message.clone();
+
+        press(&mut app, KeyCode::Char('/'));
+        assert_eq!(app.mode, Mode::Search);
+        assert!(!press(&mut app, KeyCode::Enter));
+
+        assert_eq!(app.mode, Mode::Normal);
+        assert!(app.command_input.is_empty());
+        assert_eq!(app.status_message, status);
+        assert_eq!(app.filtered_crates.len(), 1);
+    }
+
+    #[test]
+    fn test_letter_jump_sorts_by_name_and_esc_restores_order() {
+        let mut app = test_app(&["tui-input", "gitui", "ratatui", "bottom"]);
+
+        press(&mut app, KeyCode::Char('\''));
+        assert_eq!(app.mode, Mode::Jump);
+        let names: Vec<&str> = app
+            .filtered_crates
+            .iter()
+            .map(|c| c.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["bottom", "gitui", "ratatui", "tui-input"]);
+
+        press(&mut app, KeyCode::Char('R'));
+        assert_eq!(app.mode, Mode::Normal);
+        assert_eq!(app.selected_crate().unwrap().name, "ratatui");
+
+        press(&mut app, KeyCode::Char('\''));
+        assert!(!app.jump_sorted);
+        press(&mut app, KeyCode::Esc);
+        assert_eq!(app.selected_crate().unwrap().name, "ratatui");
+        assert_eq!(app.filtered_crates[0].name, "bottom");
+
+        app.previous_list();
+        assert_eq!(app.filtered_crates[0].name, "tui-input");
+    }
+
+    #[test]
+    fn test_repeated_letter_and_star_jumps_move_on_and_wrap() {
+        let mut app = test_app(&["bottom", "tui-input", "tui-logger", "ratatui"]);
+
+        // Entering Jump mode sorts by name: bottom, ratatui, tui-input, tui-logger
+        press(&mut app, KeyCode::Char('\''));
+        press(&mut app, KeyCode::Char('t'));
+        assert_eq!(app.selected_crate().unwrap().name, "tui-input");
+        press(&mut app, KeyCode::Char('\''));
+        press(&mut app, KeyCode::Char('t'));
+        assert_eq!(app.selected_crate().unwrap().name, "tui-logger");
+        press(&mut app, KeyCode::Char('\''));
+        press(&mut app, KeyCode::Char('t'));
+        assert_eq!(app.selected_crate().unwrap().name, "tui-input");
+
+        press(&mut app, KeyCode::Char('*'));
+        assert!(app.status_message.starts_with("No search to repeat"));
+        app.last_search = "tui".to_string();
+        press(&mut app, KeyCode::Char('*'));
+        assert_eq!(app.selected_crate().unwrap().name, "tui-logger");
+        press(&mut app, KeyCode::Char('*'));
+        assert_eq!(app.selected_crate().unwrap().name, "ratatui");
+        assert!(app.status_message.ends_with("(wrapped)"));
+        app.last_search = "logger".to_string();
+        press(&mut app, KeyCode::Char('*'));
+        assert_eq!(app.selected_crate().unwrap().name, "tui-logger");
+
+        // g and G still go to the top and bottom
+        press(&mut app, KeyCode::Char('G'));
+        assert_eq!(app.selected_crate().unwrap().name, "tui-logger");
+        press(&mut app, KeyCode::Char('g'));
+        assert_eq!(app.selected_crate().unwrap().name, "bottom");
+    }
+
+    #[test]
+    fn test_star_follows_fuzzy_and_description_matches() {
+        let mut app = app_with(vec![
+            package(0, "gitui"),
+            package(1, "ratatui"),
+            CratePackage {
+                description: "Terminal file manager".to_string(),
+                ..package(2, "yazi")
+            },
+        ]);
+
+        // Only a fuzzy name match: "ratui" is not a substring of "ratatui"
+        app.command_input = "search ratui".to_string();
+        app.execute_command();
+        app.reset_list();
+        app.list_state.select(Some(0));
+        press(&mut app, KeyCode::Char('*'));
+        assert_eq!(app.selected_crate().unwrap().name, "ratatui");
+
+        // Only a description match
+        press(&mut app, KeyCode::Char('/'));
+        for c in "manager".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+        press(&mut app, KeyCode::Enter);
+        app.reset_list();
+        app.list_state.select(Some(0));
+        press(&mut app, KeyCode::Char('*'));
+        assert_eq!(app.selected_crate().unwrap().name, "yazi");
+    }
+
+    #[test]
+    fn test_scroll_margin_keeps_rows_around_the_selection() {
+        let names: Vec<String> = (0..30).map(|i| format!("crate-{:02}", i)).collect();
+        let names: Vec<&str> = names.iter().map(String::as_str).collect();
+        let mut app = test_app(&names);
+        assert_eq!(app.scroll_margin, 2);
+
+        for _ in 0..8 {
+            app.next();
+            app.keep_selection_in_view(10, 30);
+        }
+        assert_eq!(app.list_state.offset(), 1);
+
+        for _ in 0..5 {
+            app.previous();
+            app.keep_selection_in_view(10, 30);
+        }
+        assert_eq!(app.list_state.offset(), 1);
+        app.previous();
+        app.keep_selection_in_view(10, 30);
+        assert_eq!(app.list_state.offset(), 0);
+
+        // Near the end the offset stops at the last full page
+        app.list_state.select(Some(29));
+        app.keep_selection_in_view(10, 30);
+        assert_eq!(app.list_state.offset(), 20);
+    }
+
+    #[test]
+    fn test_orphaned_favorites_are_kept_and_reported() {
+        let mut favorites = Favorites::default();
+        favorites.toggle(&package(0, "old-name"));
+        favorites.toggle(&package(7, "yanked"));
+        favorites.toggle(&package(9, "gitui"));
+        let mut app = App::new(
+            CratesData::from_crates(vec![package(0, "ratatui"), package(1, "gitui")]),
+            &icons::ASCII,
+            Theme::default(),
+            &Config::default(),
+            &Session::default(),
+            favorites,
+            None,
+        );
+
+        assert_eq!(app.favorites.entries[0].name, "ratatui");
+        let orphans: Vec<&str> = app
+            .orphaned_favorites()
+            .iter()
+            .map(|f| f.name.as_str())
+            .collect();
+        assert_eq!(orphans, vec!["yanked"]);
+        // Renumbered in the dataset, so matched again by name
+        assert_eq!(app.favorites.entries[2].id, "1");
+
+        app.show_favorites();
+        assert_eq!(app.filtered_crates.len(), 2);
+        assert!(app.status_message.contains("(1 not in dataset)"));
+        assert_eq!(app.favorites.entries.len(), 3);
+    }
+
+    #[test]
+    fn test_initial_search_filters_and_selects_first_match() {
+        let data = CratesData::from_crates(vec![
+            package(0, "ratatui"),
+            package(1, "tui-input"),
+            package(2, "tokio-tui"),
+        ]);
+        let launch = |query| {
+            App::new(
+                data.clone(),
+                &icons::ASCII,
+                Theme::default(),
+                &Config::default(),
+                &Session::default(),
+                Favorites::default(),
+                Some(query),
+            )
+        };
+
+        let app = launch("Tokio");
+        assert_eq!(app.filtered_crates.len(), 1);
+        assert_eq!(app.selected_crate().unwrap().name, "tokio-tui");
+
+        let app = launch("serde");
+        assert_eq!(app.filtered_crates.len(), 3);
+        assert!(app.status_message.starts_with("No crates match 'serde'"));
+    }
+
+    #[test]
+    fn test_category_browser_filters_on_enter() {
+        let tagged = |id, name: &str, cats: &[&str]| CratePackage {
+            categories: Some(cats.iter().map(|c| c.to_string()).collect()),
+            ..package(id, name)
+        };
+        let mut app = app_with(vec![
+            tagged(0, "ratatui", &["tui", "widgets"]),
+            tagged(1, "tui-input", &["widgets"]),
+            package(2, "gitui"),
+        ]);
+        let rows: Vec<(Option<&str>, usize)> = app
+            .categories
+            .iter()
+            .map(|(cat, n)| (cat.as_deref(), *n))
+            .collect();
+        assert_eq!(
+            rows,
+            vec![(Some("widgets"), 2), (Some("tui"), 1), (None, 1)]
+        );
+
+        press(&mut app, KeyCode::Char('C'));
+        assert_eq!(app.view, View::Categories);
+        press(&mut app, KeyCode::Char('G'));
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.view, View::List);
+        assert_eq!(app.filtered_crates.len(), 1);
+        assert_eq!(app.filtered_crates[0].name, "gitui");
+    }
+
+    #[test]
+    fn test_recently_viewed_tracks_dwell_and_jumps_back() {
+        let mut app = test_app(&["ratatui", "tui-input", "gitui"]);
+        let start = Instant::now();
+
+        app.track_viewed(start);
+        app.track_viewed(start + VIEW_DWELL);
+        app.next();
+        app.track_viewed(start + VIEW_DWELL);
+        app.next();
+        app.track_viewed(start + VIEW_DWELL * 2);
+        app.track_viewed(start + VIEW_DWELL * 3);
+        assert_eq!(app.recently_viewed, vec!["2", "0"]);
+        assert_eq!(app.to_session().recently_viewed, vec!["2", "0"]);
+
+        app.command_input = "search input".to_string();
+        app.execute_command();
+        press(&mut app, KeyCode::Char('v'));
+        assert!(app.popup.is_some());
+        press(&mut app, KeyCode::Char('2'));
+        assert!(app.popup.is_none());
+        assert_eq!(app.selected_crate().unwrap().name, "ratatui");
+        assert_eq!(app.filtered_crates.len(), 3);
+    }
+
+    #[test]
+    fn test_offline_refuses_to_refresh() {
+        let mut app = test_app(&["ratatui"]);
+        app.offline = true;
+        app.command_input = "refresh".to_string();
+        app.execute_command();
+        assert!(!app.is_refreshing());
+        assert!(app.status_message.starts_with("Offline:"));
+
+        // The cache is all there is offline, so it must not be deleted
+        app.command_input = "clear-cache".to_string();
+        app.execute_command();
+        assert!(!app.is_refreshing());
+        assert!(app.status_message.starts_with("Offline:"));
+    }
+
+    #[test]
+    fn test_open_keys_report_missing_links() {
+        let mut app = test_app(&["ratatui"]);
+        press(&mut app, KeyCode::Char('o'));
+        assert_eq!(app.status_message, "No repository URL for this crate");
+        press(&mut app, KeyCode::Char('O'));
+        assert_eq!(app.status_message, "No documentation URL for this crate");
+        press(&mut app, KeyCode::Char('H'));
+        assert_eq!(app.status_message, "No homepage URL for this crate");
+    }
+
+    #[test]
+    fn test_mixed_case_categories_are_merged() {
+        let tagged = |id, name: &str, cats: &[&str]| CratePackage {
+            categories: Some(cats.iter().map(|c| c.to_string()).collect()),
+            ..package(id, name)
+        };
+        let mut app = app_with(vec![
+            tagged(0, "clap-tui", &["CLI", " cli "]),
+            tagged(1, "tui-input", &["cli", "Widgets"]),
+            tagged(2, "gitui", &["Cli"]),
+            tagged(3, "blank", &["  "]),
+        ]);
+
+        assert_eq!(app.category_counts.get("cli"), Some(&3));
+        assert_eq!(app.category_label(Some("cli")), "CLI");
+        assert_eq!(app.categories.last(), Some(&(None, 1)));
+
+        app.command_input = "category cLi".to_string();
+        app.execute_command();
+        assert_eq!(app.filtered_crates.len(), 3);
+        assert!(app.status_message.ends_with("in CLI"));
+    }
+
+    #[test]
+    fn test_category_without_a_name_lists_categories_but_not_uncategorized() {
+        let tagged = |id, name: &str, cats: &[&str]| CratePackage {
+            categories: Some(cats.iter().map(|c| c.to_string()).collect()),
+            ..package(id, name)
+        };
+        let mut app = app_with(vec![
+            tagged(0, "tui-input", &["Widgets", "cli"]),
+            tagged(1, "gitui", &["CLI"]),
+            tagged(2, "blank", &["  "]),
+            package(3, "untagged"),
+        ]);
+        assert_eq!(app.categories.last(), Some(&(None, 2)));
+
+        app.command_input = "category".to_string();
+        app.execute_command();
+        assert_eq!(
+            app.status_message,
+            "2 categories (:category <name>): cli, Widgets"
+        );
+    }
+
+    #[test]
+    fn test_long_name_is_truncated_in_a_narrow_list() {
+        use ratatui::backend::TestBackend;
+
+        let long_name = "a".repeat(200);
+        let mut app = test_app(&[&long_name, "gitui"]);
+        let mut terminal = Terminal::new(TestBackend::new(24, 8)).unwrap();
+        terminal
+            .draw(|f| render_list(f, &mut app, f.area()))
+            .unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let row = |y: u16| -> String {
+            (0..buffer.area.width)
+                .map(|x| buffer[(x, y)].symbol())
+                .collect()
+        };
+        // Border, marker, icon and name fill the row exactly, ending in an ellipsis
+        assert!(row(1).ends_with("a…│"), "row was {:?}", row(1));
+        assert!(row(2).contains("v 0"), "stats line moved: {:?}", row(2));
+        assert!(row(3).contains("gitui"));
+
+        // The detail pane still gets the full name
+        assert_eq!(app.selected_crate().unwrap().name, long_name);
+    }
+
+    #[test]
+    fn test_large_list_renders_only_the_visible_window() {
+        use ratatui::backend::TestBackend;
+
+        let names: Vec<String> = (0..5_000).map(|i| format!("crate-{}", i)).collect();
+        let names: Vec<&str> = names.iter().map(String::as_str).collect();
+        let mut app = test_app(&names);
+        app.gutter = false;
+        app.list_state.select(Some(4_000));
+
+        let mut terminal = Terminal::new(TestBackend::new(30, 12)).unwrap();
+        terminal
+            .draw(|f| render_list(f, &mut app, f.area()))
+            .unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let text: String = (0..buffer.area.height)
+            .flat_map(|y| (0..buffer.area.width).map(move |x| (x, y)))
+            .map(|pos| buffer[pos].symbol())
+            .collect();
+        // Selection and scroll margin behave as before: the selection plus 2 rows below it
+        assert!(text.contains("> # crate-4000"), "{}", text);
+        assert!(text.contains("crate-4002"));
+        assert!(!text.contains("crate-4003"));
+        assert_eq!(app.list_state.offset(), 3_998);
+        assert_eq!(app.list_state.selected(), Some(4_000));
+    }
+
+    #[test]
+    fn test_crate_categories_popup_filters_on_enter() {
+        let mut app = app_with(vec![
+            CratePackage {
+                categories: Some(vec!["TUI".into(), "tui".into(), "Widgets".into()]),
+                ..package(0, "ratatui")
+            },
+            CratePackage {
+                categories: Some(vec!["widgets".into()]),
+                ..package(1, "tui-input")
+            },
+            package(2, "gitui"),
+        ]);
+
+        press(&mut app, KeyCode::Char('t'));
+        let popup = app.popup.as_ref().expect("categories popup");
+        assert_eq!(
+            popup.picks,
+            vec![
+                Pick::Category("tui".into()),
+                Pick::Category("widgets".into())
+            ]
+        );
+
+        press(&mut app, KeyCode::Char('j'));
+        assert_eq!(app.popup.as_ref().map(|p| p.selected), Some(1));
+        press(&mut app, KeyCode::Enter);
+        assert!(app.popup.is_none());
+        assert_eq!(app.filtered_crates.len(), 2);
+        assert!(app.status_message.ends_with("in Widgets"));
+    }
+
+    #[test]
+    fn test_refresh_deltas_join_by_id_and_clear_on_navigation() {
+        let with_downloads = |id, name: &str, downloads| CratePackage {
+            downloads,
+            ..package(id, name)
+        };
+        let mut app = app_with(vec![
+            with_downloads(0, "ratatui", 1_000),
+            with_downloads(1, "gitui", 500),
+        ]);
+        let (tx, rx) = mpsc::channel();
+        tx.send(Ok(CratesData::from_crates(vec![
+            with_downloads(0, "ratatui", 13_000),
+            with_downloads(1, "gitui", 500),
+            with_downloads(2, "new-crate", 10),
+        ])))
+        .unwrap();
+        app.refresh_rx = Some(rx);
+        app.begin_task();
+        app.poll_refresh();
+
+        assert_eq!(
+            app.download_deltas,
+            HashMap::from([("0".to_string(), 12_000)])
+        );
+        let span = app.download_delta_span("0").unwrap();
+        assert_eq!(span.content, " up +12.0K");
+
+        press(&mut app, KeyCode::Char('j'));
+        assert!(app.download_deltas.is_empty());
+    }
+
+    #[test]
+    fn test_stats_charts_scale_with_the_pane() {
+        use ratatui::backend::TestBackend;
+
+        let mut app = app_with(vec![
+            CratePackage {
+                downloads: 4_000,
+                ..package(0, "ratatui")
+            },
+            CratePackage {
+                downloads: 1_000,
+                ..package(1, "gitui")
+            },
+        ]);
+        app.metadata.core_libraries = 1;
+        app.metadata.community_packages = 1;
+
+        // Length of the bar drawn after `label`, in the narrow and the two-column layout
+        let bar_lengths = |app: &App, label: &str| -> Vec<usize> {
+            [50, 120]
+                .map(|width| {
+                    let mut terminal = Terminal::new(TestBackend::new(width, 40)).unwrap();
+                    terminal.draw(|f| render_stats(f, app, f.area())).unwrap();
+                    let buffer = terminal.backend().buffer();
+                    let row = (0..buffer.area.height)
+                        .map(|y| {
+                            (0..buffer.area.width)
+                                .map(|x| buffer[(x, y)].symbol())
+                                .collect::<String>()
+                        })
+                        .find(|row| row.contains(label))
+                        .unwrap();
+                    row.matches(app.icons.bar).count()
+                })
+                .to_vec()
+        };
+
+        let core = bar_lengths(&app, "Core      50%");
+        assert!(core[0] > 0 && core[1] > core[0]);
+        let top = bar_lengths(&app, "4.0K");
+        let second = bar_lengths(&app, "1.0K");
+        assert!(top[1] > top[0]);
+        assert!(second[0] < top[0] && second[1] < top[1]);
+
+        // The top 5 shows up once, as the chart
+        let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+        terminal.draw(|f| render_stats(f, &app, f.area())).unwrap();
+        let screen: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert_eq!(screen.matches("Top 5 Most Downloaded").count(), 1);
+
+        // The ASCII icon set draws the same bars with `#`
+        app.icons = &icons::ASCII;
+        assert_eq!(bar_lengths(&app, "Core      50%"), core);
+        assert_eq!(bar_lengths(&app, "4.0K"), top);
+    }
+
+    #[test]
+    fn test_loading_screen_offers_retry_after_an_error() {
+        use ratatui::backend::TestBackend;
+
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        let screen = |terminal: &Terminal<TestBackend>| -> String {
+            let buffer = terminal.backend().buffer();
+            buffer.content().iter().map(|cell| cell.symbol()).collect()
+        };
+
+        terminal
+            .draw(|f| render_loading(f, &icons::ASCII, &Theme::default(), 1, None, None))
+            .unwrap();
+        assert!(screen(&terminal).contains("/ Loading Ratcrate data..."));
+
+        // A known size fills a gauge; an unknown one counts bytes next to the spinner
+        terminal
+            .draw(|f| {
+                let progress = Some((512 * 1024, Some(2 * 1024 * 1024)));
+                render_loading(f, &icons::ASCII, &Theme::default(), 1, progress, None)
+            })
+            .unwrap();
+        assert!(screen(&terminal).contains("512.0 KB of 2.0 MB"));
+        terminal
+            .draw(|f| {
+                let progress = Some((3 * 1024, None));
+                render_loading(f, &icons::ASCII, &Theme::default(), 1, progress, None)
+            })
+            .unwrap();
+        assert!(screen(&terminal).contains("/ Downloading Ratcrate data... 3.0 KB"));
+
+        terminal
+            .draw(|f| {
+                render_loading(
+                    f,
+                    &icons::ASCII,
+                    &Theme::default(),
+                    1,
+                    None,
+                    Some("Failed to download data"),
+                )
+            })
+            .unwrap();
+        let text = screen(&terminal);
+        assert!(text.contains("Failed to download data"));
+        assert!(text.contains("r to retry, q to quit"));
+    }
+
+    #[test]
+    fn test_refresh_keeps_the_search_and_selection() {
+        let mut app = test_app(&["ratatui", "gitui", "serde"]);
+        app.apply_list_op(ListOp::Search("ui".into()));
+        press(&mut app, KeyCode::Char('j'));
+        assert_eq!(app.selected_crate().unwrap().name, "gitui");
+
+        let (tx, rx) = mpsc::channel();
+        tx.send(Err(anyhow::anyhow!("network down"))).unwrap();
+        app.refresh_rx = Some(rx);
+        app.begin_task();
+        app.poll_refresh();
+        assert_eq!(
+            app.status_message,
+            format!("{} Refresh failed: network down", app.icons.error)
+        );
+        assert_eq!(app.all_crates.len(), 3);
+
+        // The refreshed dataset renumbered gitui, so it is found again by name
+        let (tx, rx) = mpsc::channel();
+        tx.send(Ok(CratesData::from_crates(vec![
+            package(0, "ratatui"),
+            package(3, "tui-new"),
+            package(7, "gitui"),
+            package(2, "serde"),
+        ])))
+        .unwrap();
+        app.refresh_rx = Some(rx);
+        app.begin_task();
+        app.poll_refresh();
+        let names: Vec<&str> = app
+            .filtered_crates
+            .iter()
+            .map(|c| c.name.as_str())
+            .collect();
+        assert_eq!(names, ["ratatui", "tui-new", "gitui"]);
+        assert_eq!(app.selected_crate().unwrap().name, "gitui");
+    }
+
+    #[test]
+    fn test_search_preview_counts_without_filtering() {
+        let mut app = test_app(&["ratatui", "tui-input", "gitui"]);
+
+        press(&mut app, KeyCode::Char(':'));
+        for c in "search ".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+        assert_eq!(app.search_preview, None);
+        for c in "inp".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+        assert_eq!(app.search_preview, Some(1));
+        press(&mut app, KeyCode::Backspace);
+        press(&mut app, KeyCode::Backspace);
+        assert_eq!(app.search_preview, Some(3));
+        assert_eq!(app.filtered_crates.len(), 3);
+
+        press(&mut app, KeyCode::Esc);
+        assert_eq!(app.search_preview, None);
+    }
+
+    #[test]
+    fn test_up_and_down_recall_earlier_commands() {
+        let mut app = test_app(&["ratatui", "gitui"]);
+        for cmd in ["top 5", "", "top 5", "new 3"] {
+            press(&mut app, KeyCode::Char(':'));
+            for c in cmd.chars() {
+                press(&mut app, KeyCode::Char(c));
+            }
+            press(&mut app, KeyCode::Enter);
+        }
+        // Blank entries and consecutive repeats are not stored
+        assert_eq!(app.command_history, ["top 5", "new 3"]);
+
+        press(&mut app, KeyCode::Char(':'));
+        press(&mut app, KeyCode::Up);
+        assert_eq!(app.command_input, "new 3");
+        press(&mut app, KeyCode::Up);
+        press(&mut app, KeyCode::Up);
+        assert_eq!(app.command_input, "top 5");
+        press(&mut app, KeyCode::Down);
+        assert_eq!(app.command_input, "new 3");
+        press(&mut app, KeyCode::Down);
+        assert_eq!(app.command_input, "");
+
+        // Leaving command mode midway starts the next recall from the newest entry
+        press(&mut app, KeyCode::Up);
+        press(&mut app, KeyCode::Up);
+        press(&mut app, KeyCode::Esc);
+        press(&mut app, KeyCode::Char(':'));
+        press(&mut app, KeyCode::Up);
+        assert_eq!(app.command_input, "new 3");
+    }
+
+    #[test]
+    fn test_navigation_on_an_empty_list_is_a_no_op() {
+        use ratatui::backend::TestBackend;
+
+        let mut app = test_app(&["ratatui", "gitui"]);
+        app.command_input = "search zzzz".to_string();
+        app.execute_command();
+        assert!(app.filtered_crates.is_empty());
+        assert_eq!(app.list_state.selected(), None);
+
+        for code in [
+            KeyCode::Char('j'),
+            KeyCode::Char('k'),
+            KeyCode::Down,
+            KeyCode::Up,
+            KeyCode::Char('g'),
+            KeyCode::Char('G'),
+        ] {
+            press(&mut app, code);
+            assert_eq!(app.list_state.selected(), None, "{:?}", code);
+        }
+        for c in ['d', 'u'] {
+            handle_key(
+                &mut app,
+                KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL),
+            );
+            assert_eq!(app.list_state.selected(), None, "Ctrl-{}", c);
+        }
+
+        // The detail pane copes with nothing selected
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        terminal.draw(|f| ui(f, &mut app)).unwrap();
+    }
+
+    #[test]
+    fn test_r_reverses_the_current_list() {
+        let mut app = test_app(&["a", "b", "c"]);
+        app.list_state.select(Some(1));
+        press(&mut app, KeyCode::Char('r'));
+        let names: Vec<&str> = app
+            .filtered_crates
+            .iter()
+            .map(|c| c.name.as_str())
+            .collect();
+        assert_eq!(names, ["c", "b", "a"]);
+        assert_eq!(app.list_state.selected(), Some(0));
+        assert!(!app.is_filtered());
+
+        // A second press flips back rather than stacking reversals
+        press(&mut app, KeyCode::Char('r'));
+        assert_eq!(app.filtered_crates[0].name, "a");
+        assert!(!app.list_ops.contains(&ListOp::Reverse));
+
+        app.set_filtered(vec![]);
+        app.reset_selection();
+        press(&mut app, KeyCode::Char('r'));
+        assert_eq!(app.list_state.selected(), None);
+    }
+
+    #[test]
+    fn test_toggling_favorites_keeps_prev_history_bounded() {
+        let mut app = test_app(&["a", "b", "c"]);
+        app.favorites.toggle(&package(1, "b"));
+
+        for _ in 0..LIST_HISTORY_DEPTH * 2 {
+            press(&mut app, KeyCode::Char('F'));
+        }
+        assert_eq!(app.list_history.len(), LIST_HISTORY_DEPTH);
+    }
+
+    #[test]
+    fn test_tab_completes_commands_and_categories() {
+        let mut a = package(0, "ratatui");
+        a.categories = Some(vec!["Widgets".to_string()]);
+        let mut b = package(1, "gitui");
+        b.categories = Some(vec!["Games".to_string(), "Git tools".to_string()]);
+        let mut app = app_with(vec![a, b]);
+
+        press(&mut app, KeyCode::Char(':'));
+        for c in "comp".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+        press(&mut app, KeyCode::Tab);
+        assert_eq!(app.command_input, "compat ");
+
+        // Several matches cycle on repeated Tab
+        app.command_input = "co".to_string();
+        press(&mut app, KeyCode::Tab);
+        assert_eq!(app.command_input, "compat");
+        let candidates = app.completion.as_ref().unwrap().1.clone();
+        assert_eq!(
+            candidates,
+            ["compat", "copylist", "copyview", "core", "count"]
+        );
+        press(&mut app, KeyCode::Tab);
+        assert_eq!(app.command_input, "copylist");
+        for _ in 0..4 {
+            press(&mut app, KeyCode::Tab);
+        }
+        assert_eq!(app.command_input, "compat");
 
-    let total_weekly: u64 = app.all_crates.iter().map(|c| c.recent_downloads).sum();
+        // Typing ends the cycle
+        press(&mut app, KeyCode::Char(' '));
+        assert_eq!(app.completion, None);
 
-    // Top 5 by downloads
-    let mut sorted_by_downloads = app.all_crates.clone();
-    sorted_by_downloads.sort_by(|a, b| b.downloads.cmp(&a.downloads));
-    let top_5 = sorted_by_downloads.iter().take(5);
+        app.command_input = "category g".to_string();
+        press(&mut app, KeyCode::Tab);
+        assert_eq!(app.command_input, "category Games");
+        press(&mut app, KeyCode::Tab);
+        assert_eq!(app.command_input, "category Git tools");
+        press(&mut app, KeyCode::Tab);
+        assert_eq!(app.command_input, "category Games");
+    }
 
-    let mut lines = vec![];
+    #[test]
+    fn test_slash_filters_as_you_type() {
+        let mut app = test_app(&["ratatui", "tui-input", "gitui"]);
+        app.list_state.select(Some(2));
 
-    // Banner
-    lines.push(Line::from(""));
-    lines.push(Line::from(Span::styled(
-        "╔═══════════════════════════════════════════════════════╗",
-        Style::default()
-            .fg(Color::Cyan)
-            .add_modifier(Modifier::BOLD),
-    )));
-    lines.push(Line::from(vec![
-        Span::styled(
-            "║   ",
-            Style::default()
-                .fg(Color::Cyan)
-                .add_modifier(Modifier::BOLD),
-        ),
-        Span::styled(
-            "📊 RATATUI ECOSYSTEM STATISTICS",
-            Style::default()
-                .fg(Color::Yellow)
-                .add_modifier(Modifier::BOLD),
-        ),
-        Span::styled(
-            "            ║",
-            Style::default()
-                .fg(Color::Cyan)
-                .add_modifier(Modifier::BOLD),
-        ),
-    ]));
-    lines.push(Line::from(Span::styled(
-        "╚═══════════════════════════════════════════════════════╝",
-        Style::default()
-            .fg(Color::Cyan)
-            .add_modifier(Modifier::BOLD),
-    )));
-    lines.push(Line::from(""));
+        press(&mut app, KeyCode::Char('/'));
+        for c in "inp".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+        assert_eq!(app.filtered_crates.len(), 1);
+        assert_eq!(app.filtered_crates[0].name, "tui-input");
+        for _ in 0..3 {
+            press(&mut app, KeyCode::Backspace);
+        }
+        assert_eq!(app.filtered_crates.len(), 3);
 
-    // Overview
-    lines.push(Line::from(Span::styled(
-        "📦 Overview:",
-        Style::default()
-            .fg(Color::Green)
-            .add_modifier(Modifier::BOLD),
-    )));
-    lines.push(Line::from(vec![
-        Span::raw("  Total Packages:     "),
-        Span::styled(
-            format!("{}", total),
-            Style::default()
-                .fg(Color::Cyan)
-                .add_modifier(Modifier::BOLD),
-        ),
-    ]));
-    lines.push(Line::from(vec![
-        Span::raw("  ⭐ Core Libraries:  "),
-        Span::styled(
-            format!("{}", core),
-            Style::default()
-                .fg(Color::Yellow)
-                .add_modifier(Modifier::BOLD),
-        ),
-    ]));
-    lines.push(Line::from(vec![
-        Span::raw("  🌍 Community:       "),
-        Span::styled(
-            format!("{}", community),
-            Style::default()
-                .fg(Color::Green)
-                .add_modifier(Modifier::BOLD),
-        ),
-    ]));
-    lines.push(Line::from(""));
+        // Esc puts back the list and selection from before '/'
+        press(&mut app, KeyCode::Char('g'));
+        press(&mut app, KeyCode::Esc);
+        assert_eq!(app.mode, Mode::Normal);
+        assert_eq!(app.filtered_crates.len(), 3);
+        assert_eq!(app.list_state.selected(), Some(2));
 
-    // Download stats
-    lines.push(Line::from(Span::styled(
-        "📈 Download Statistics:",
-        Style::default()
-            .fg(Color::Green)
-            .add_modifier(Modifier::BOLD),
-    )));
-    lines.push(Line::from(vec![
-        Span::raw("  Total Downloads:    "),
-        Span::styled(
-            format_number(total_downloads),
-            Style::default()
-                .fg(Color::Green)
-                .add_modifier(Modifier::BOLD),
-        ),
-    ]));
-    lines.push(Line::from(vec![
-        Span::raw("  Average/Crate:      "),
-        Span::styled(
-            format_number(avg_downloads),
-            Style::default()
-                .fg(Color::Blue)
-                .add_modifier(Modifier::BOLD),
-        ),
-    ]));
-    lines.push(Line::from(vec![
-        Span::raw("  Weekly Downloads:   "),
-        Span::styled(
-            format_number(total_weekly),
-            Style::default()
-                .fg(Color::Magenta)
-                .add_modifier(Modifier::BOLD),
-        ),
-    ]));
-    lines.push(Line::from(""));
+        // Enter keeps the results, with the earlier list one :prev away
+        press(&mut app, KeyCode::Char('/'));
+        for c in "git".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.mode, Mode::Normal);
+        assert_eq!(app.filtered_crates.len(), 1);
+        assert_eq!(app.filtered_crates[0].name, "gitui");
+        assert_eq!(app.status_message, "Found 1 crates matching 'git'");
+        app.previous_list();
+        assert_eq!(app.filtered_crates.len(), 3);
+    }
 
-    // Simple bar chart
-    lines.push(Line::from(Span::styled(
-        "📊 Distribution:",
-        Style::default()
-            .fg(Color::Green)
-            .add_modifier(Modifier::BOLD),
-    )));
+    #[test]
+    fn test_try_asks_before_running_and_can_be_cancelled() {
+        let mut app = test_app(&["ratatui"]);
+        app.command_input = "try".to_string();
+        app.execute_command();
+        assert_eq!(app.mode, Mode::Confirm);
+        assert!(app.status_message.contains("cargo add ratatui"));
+        assert!(matches!(app.pending_action, Some(PendingAction::Run(_))));
 
-    let core_pct = (core as f64 / total as f64 * 100.0) as usize;
-    let community_pct = 100 - core_pct;
+        press(&mut app, KeyCode::Esc);
+        assert_eq!(app.mode, Mode::Normal);
+        assert!(app.pending_action.is_none() && app.plan_rx.is_none());
 
-    let core_bar = "█".repeat(core_pct / 2);
-    let community_bar = "█".repeat(community_pct / 2);
+        // Outside a Cargo project nothing is planned at all
+        app.command_input = "add /".to_string();
+        app.execute_command();
+        assert_eq!(app.mode, Mode::Normal);
+        assert!(app.pending_action.is_none());
+    }
 
-    lines.push(Line::from(vec![
-        Span::raw("  Core:      ["),
-        Span::styled(core_bar, Style::default().fg(Color::Yellow)),
-        Span::raw(format!("] {}%", core_pct)),
-    ]));
-    lines.push(Line::from(vec![
-        Span::raw("  Community: ["),
-        Span::styled(community_bar, Style::default().fg(Color::Green)),
-        Span::raw(format!("] {}%", community_pct)),
-    ]));
-    lines.push(Line::from(""));
+    #[test]
+    fn test_view_text_serializes_list_and_stats() {
+        let mut app = app_with(vec![
+            CratePackage {
+                downloads: 1_500,
+                ..package(0, "ratatui")
+            },
+            CratePackage {
+                downloads: 20,
+                ..package(1, "gitui")
+            },
+        ]);
 
-    // Top 5
-    lines.push(Line::from(Span::styled(
-        "🏆 Top 5 Most Downloaded:",
-        Style::default()
-            .fg(Color::Green)
-            .add_modifier(Modifier::BOLD),
-    )));
+        let (text, what) = app.view_text().unwrap();
+        assert_eq!(what, "list of 2 crates");
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[0], "Crates (2/2)");
+        assert!(lines[2].starts_with("ratatui") && lines[2].contains("1.5K"));
+        assert!(lines[3].starts_with("gitui  "));
 
-    for (i, crate_pkg) in top_5.enumerate() {
-        let medal = match i {
-            0 => "🥇",
-            1 => "🥈",
-            2 => "🥉",
-            _ => "  ",
-        };
+        app.view = View::Stats;
+        let (text, _) = app.view_text().unwrap();
+        assert!(text.contains("Total Packages:     2"));
+        assert!(text.contains("Top 5 Most Downloaded:"));
 
-        lines.push(Line::from(vec![
-            Span::raw(format!("  {} ", medal)),
-            Span::styled(
-                format!("{:20}", crate_pkg.name),
-                Style::default()
-                    .fg(Color::Cyan)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::raw(" "),
-            Span::styled(
-                format!("{:>10}", format_number(crate_pkg.downloads)),
-                Style::default().fg(Color::Green),
-            ),
-        ]));
+        app.view = View::Help;
+        assert!(app.view_text().is_none());
     }
 
-    lines.push(Line::from(""));
-    lines.push(Line::from(Span::styled(
-        "💡 Tip: Press TAB to go back to list view",
-        Style::default().fg(Color::DarkGray),
-    )));
+    #[test]
+    fn test_dumb_or_redirected_terminals_are_not_interactive() {
+        assert!(is_interactive(Some("xterm-256color"), true));
+        assert!(is_interactive(None, true));
+        assert!(!is_interactive(Some("dumb"), true));
+        assert!(!is_interactive(Some("xterm-256color"), false));
+    }
 
-    let paragraph = Paragraph::new(Text::from(lines))
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Cyan))
-                .title(vec![
-                    Span::styled(" 📊 ", Style::default().fg(Color::Yellow)),
-                    Span::styled(
-                        "Statistics",
-                        Style::default()
-                            .fg(Color::Cyan)
-                            .add_modifier(Modifier::BOLD),
-                    ),
-                ])
-                .style(Style::default()),
-        )
-        .wrap(Wrap { trim: false });
+    #[test]
+    fn test_brackets_hop_between_favorites_and_wrap() {
+        let mut app = test_app(&["a", "b", "c", "d", "e"]);
+        app.favorites.toggle(&package(1, "b"));
+        app.favorites.toggle(&package(3, "d"));
 
-    f.render_widget(paragraph, area);
-}
+        press(&mut app, KeyCode::Char(']'));
+        assert_eq!(app.list_state.selected(), Some(1));
+        press(&mut app, KeyCode::Char(']'));
+        assert_eq!(app.list_state.selected(), Some(3));
+        press(&mut app, KeyCode::Char(']'));
+        assert_eq!(app.list_state.selected(), Some(1));
+        press(&mut app, KeyCode::Char('['));
+        assert_eq!(app.list_state.selected(), Some(3));
 
-fn render_command_bar(f: &mut Frame, app: &App, area: Rect) {
-    let text = match app.mode {
-        Mode::Normal => Text::from(Line::from(vec![
-            Span::styled(
-                " NORMAL ",
-                Style::default()
-                    .bg(Color::Blue)
-                    .fg(Color::Black)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::raw(" "),
-            Span::styled(&app.status_message, Style::default().fg(Color::White)),
-        ])),
-        Mode::Command => Text::from(Line::from(vec![
-            Span::styled(
-                " COMMAND ",
-                Style::default()
-                    .bg(Color::Green)
-                    .fg(Color::Black)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::styled(
-                " :",
-                Style::default()
-                    .fg(Color::Green)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::styled(&app.command_input, Style::default().fg(Color::Yellow)),
-            Span::styled(
-                "_",
-                Style::default()
-                    .fg(Color::Yellow)
-                    .add_modifier(Modifier::SLOW_BLINK),
-            ),
-        ])), // Mode::Try => {
-             //     Text::from(Line::from(vec![
-             //         Span::styled(
-             //             " TRY ",
-             //             Style::default()
-             //                 .bg(Color::Magenta)
-             //                 .fg(Color::Black)
-             //                 .add_modifier(Modifier::BOLD),
-             //         ),
-             //         Span::raw(" "),
-             //         Span::styled(&app.status_message, Style::default().fg(Color::Magenta)),
-             //     ]))
-             // }
-    };
+        app.favorites = Favorites::default();
+        press(&mut app, KeyCode::Char('['));
+        assert_eq!(app.list_state.selected(), Some(3));
+        assert_eq!(app.status_message, "No favorites in this list");
+    }
 
-    let paragraph = Paragraph::new(text).block(
-        Block::default()
-            .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Cyan)),
-    );
+    #[test]
+    fn test_raw_popup_shows_json_and_scrolls_until_esc() {
+        let mut app = test_app(&["ratatui"]);
+        app.command_input = "raw".to_string();
+        app.execute_command();
 
-    f.render_widget(paragraph, area);
-}
+        let popup = app.popup.as_ref().unwrap();
+        assert_eq!(popup.title, "ratatui (raw)");
+        assert!(
+            popup
+                .lines
+                .iter()
+                .any(|l| l.to_string().contains("\"name\": \"ratatui\""))
+        );
 
-// ============================================================================
-// Event Handling
-// ============================================================================
+        press(&mut app, KeyCode::Char('j'));
+        press(&mut app, KeyCode::Char('x'));
+        assert_eq!(app.popup.as_ref().unwrap().scroll, Some(1));
+        press(&mut app, KeyCode::Esc);
+        assert!(app.popup.is_none());
+    }
 
-fn handle_events(app: &mut App) -> Result<bool> {
-    if event::poll(std::time::Duration::from_millis(100))? {
-        if let Event::Key(key) = event::read()? {
-            match app.mode {
-                Mode::Normal => match key.code {
-                    // Quit
-                    KeyCode::Char('q') => return Ok(true),
-
-                    // Navigation
-                    KeyCode::Char('j') | KeyCode::Down => app.next(),
-                    KeyCode::Char('k') | KeyCode::Up => app.previous(),
-                    KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                        app.next_page()
-                    }
-                    KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                        app.previous_page()
-                    }
-                    KeyCode::Char('g') => app.list_state.select(Some(0)),
-                    KeyCode::Char('G') => app
-                        .list_state
-                        .select(Some(app.filtered_crates.len().saturating_sub(1))),
-
-                    // Views
-                    KeyCode::Tab => {
-                        app.view = match app.view {
-                            View::List => View::Stats,
-                            View::Stats => View::List,
-                            View::Help => View::List,
-                        };
-                    }
-                    KeyCode::Char('?') => {
-                        app.view = if app.view == View::Help {
-                            View::List
-                        } else {
-                            View::Help
-                        };
-                    }
+    #[test]
+    fn test_trend_splits_on_the_recent_share_of_downloads() {
+        let with = |downloads, recent_downloads| CratePackage {
+            downloads,
+            recent_downloads,
+            ..package(0, "x")
+        };
+        assert_eq!(Trend::of(&with(1_000, 400)), Some(Trend::Growing));
+        assert_eq!(Trend::of(&with(1_000, 200)), Some(Trend::Growing));
+        assert_eq!(Trend::of(&with(1_000, 50)), Some(Trend::Stable));
+        assert_eq!(Trend::of(&with(0, 0)), None);
+    }
 
-                    // Commands
-                    KeyCode::Char(':') | KeyCode::Char('/') => {
-                        app.mode = Mode::Command;
-                        app.command_input.clear();
-                        if key.code == KeyCode::Char('/') {
-                            app.command_input.push_str("search ");
-                        }
-                    }
+    #[test]
+    fn test_dependency_kind_flags_optional_and_dev() {
+        let kind = |optional, dev_dependency| {
+            dependency_kind(&RatatuiDependency {
+                version: "^0.29".into(),
+                optional,
+                dev_dependency,
+            })
+        };
+        assert_eq!(kind(false, false), "dependency");
+        assert_eq!(kind(true, false), "optional dependency");
+        assert_eq!(kind(false, true), "dev-dependency");
+        assert_eq!(kind(true, true), "optional dev-dependency");
+    }
 
-                    _ => {}
-                },
-                Mode::Command => match key.code {
-                    KeyCode::Enter => {
-                        if app.command_input == "q" || app.command_input == "quit" {
-                            return Ok(true);
-                        }
-                        app.execute_command();
-                    }
-                    KeyCode::Char(c) => {
-                        app.command_input.push(c);
-                    }
-                    KeyCode::Backspace => {
-                        app.command_input.pop();
-                    }
-                    KeyCode::Esc => {
-                        app.mode = Mode::Normal;
-                        app.command_input.clear();
-                    }
-                    _ => {}
-                },
-                // Mode::Try => match key.code {
-                //     KeyCode::Char('y') | KeyCode::Char('Y') => {
-                //         if let Some(crate_name) = app.try_crate.clone() {
-                //             // Update status to show we're working
-                //             app.status_message = format!("🔄 Setting up try environment for {}... (this may take a moment)", crate_name);
-                //             app.mode = Mode::Normal; // Exit try mode immediately
-                //
-                //             // Force redraw to show the status
-                //             // terminal.draw(|f| ui(f, app))?;
-                //
-                //             // Now do the work
-                //             match setup_try_environment(&crate_name) {
-                //                 Ok(temp_dir) => {
-                //                     app.try_temp_dir = Some(temp_dir.clone());
-                //                     app.status_message = format!(
-                //                         "✅ Ready! Run:  cd {}  &&  cargo run  |  Cleanup:  rm -rf /tmp/ratcrate-try/{}",
-                //                         temp_dir, crate_name
-                //                     );
-                //                 }
-                //                 Err(e) => {
-                //                     app.status_message = format!("❌ Error: {}", e);
-                //                 }
-                //             }
-                //
-                //             // Redraw with final status
-                //             // terminal.draw(|f| ui(f, app))?;
-                //         } else {
-                //             app.status_message = "No crate selected for try mode".to_string();
-                //             app.mode = Mode::Normal;
-                //         }
-                //         app.try_crate = None;
-                //     }
-                //     KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
-                //         app.mode = Mode::Normal;
-                //         app.try_crate = None;
-                //         app.status_message = "Try cancelled".to_string();
-                //     }
-                //     _ => {}
-                // },
-            }
-        }
-    }
-    Ok(false)
-}
+    #[test]
+    fn test_badge_markdown_links_crates_io_and_docs_rs() {
+        assert_eq!(
+            badge_markdown("ratatui"),
+            "[![crates.io](https://img.shields.io/crates/v/ratatui.svg)](https://crates.io/crates/ratatui)\n\
+             [![docs.rs](https://img.shields.io/docsrs/ratatui)](https://docs.rs/ratatui)\n"
+        );
+    }
 
-// ============================================================================
-// Main
-// ============================================================================
+    #[test]
+    fn test_export_md_writes_the_list_as_a_table() {
+        let mut ratatui = package(0, "ratatui");
+        ratatui.downloads = 1_500;
+        ratatui.description = "Build TUIs | fast\nand easy".to_string();
+        let mut gitui = package(1, "gitui");
+        gitui.ratatui_dependency.version = "^0.29".to_string();
+        let mut app = app_with(vec![ratatui, gitui]);
 
-fn main() -> Result<()> {
-    // Load data
-    println!("Loading Ratcrate data...");
-    let data = get_data(false)?;
+        let path = std::env::temp_dir().join(format!("ratcrate-export-{}.md", std::process::id()));
+        app.command_input = format!("export md {}", path.display());
+        app.execute_command();
+        let written = fs::read_to_string(&path).unwrap();
+        let _ = fs::remove_file(&path);
 
-    // Setup terminal
-    enable_raw_mode()?;
-    let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
-    let backend = CrosstermBackend::new(stdout);
-    let mut terminal = Terminal::new(backend)?;
+        assert_eq!(
+            written,
+            "| Crate | Downloads | Ratatui | Description |\n\
+             |---|---:|---|---|\n\
+             | [ratatui](https://crates.io/crates/ratatui) | 1.5K | - | Build TUIs \\| fast and easy |\n\
+             | [gitui](https://crates.io/crates/gitui) | 0 | ^0.29 |  |\n"
+        );
+        assert!(app.status_message.contains("Exported 2 crates to"));
 
-    // Create app
-    let mut app = App::new(data);
+        app.command_input = "export csv".to_string();
+        app.execute_command();
+        assert_eq!(app.status_message, "Usage: :export md [path]");
+    }
 
-    // Run app
-    let result = run_app(&mut terminal, &mut app);
+    #[test]
+    fn test_relative_dates_read_naturally_and_fall_back_to_the_raw_text() {
+        let now = filter::parse_date("2025-06-10T12:00:00Z").unwrap();
+        assert_eq!(
+            relative_date("2025-06-07T08:00:00Z", now),
+            ("3 days ago (2025-06-07)".to_string(), Some(3))
+        );
+        assert_eq!(relative_date("2025-06-09", now).0, "1 day ago (2025-06-09)");
+        assert_eq!(relative_date("2025-06-10", now).0, "today (2025-06-10)");
+        assert_eq!(
+            relative_date("2025-03-01", now).0,
+            "3 months ago (2025-03-01)"
+        );
+        assert_eq!(
+            relative_date("2023-05-01", now).0,
+            "2 years ago (2023-05-01)"
+        );
+        assert_eq!(
+            relative_date("last tuesday", now),
+            ("last tuesday".to_string(), None)
+        );
+        assert_eq!(relative_date("", now), ("unknown".to_string(), None));
+    }
 
-    // Restore terminal
-    disable_raw_mode()?;
-    execute!(
-        terminal.backend_mut(),
-        LeaveAlternateScreen,
-        DisableMouseCapture
-    )?;
-    terminal.show_cursor()?;
+    #[test]
+    fn test_select_top_result_picks_the_most_downloaded_match() {
+        let crates = vec![
+            CratePackage {
+                downloads: 10,
+                ..package(0, "tui-a")
+            },
+            CratePackage {
+                downloads: 900,
+                ..package(1, "tui-b")
+            },
+            CratePackage {
+                downloads: 900,
+                ..package(2, "tui-c")
+            },
+        ];
+        let mut app = app_with(crates.clone());
+        app.apply_list_op(ListOp::Search("tui".into()));
+        assert_eq!(app.list_state.selected(), Some(0));
 
-    result
-}
+        let mut config = Config::default();
+        config.ui.select_top_result = true;
+        let mut app = App::new(
+            CratesData::from_crates(crates),
+            &icons::ASCII,
+            Theme::default(),
+            &config,
+            &Session::default(),
+            Favorites::default(),
+            None,
+        );
+        app.apply_list_op(ListOp::Search("tui".into()));
+        assert_eq!(app.list_state.selected(), Some(1));
+    }
 
-fn run_app(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, app: &mut App) -> Result<()> {
-    loop {
-        terminal.draw(|f| ui(f, app))?;
+    #[test]
+    fn test_digit_removes_one_filter_chip() {
+        let mut app = app_with(vec![
+            CratePackage {
+                downloads: 50_000,
+                ..package(0, "ratatui")
+            },
+            CratePackage {
+                downloads: 10,
+                ..package(1, "tui-input")
+            },
+            CratePackage {
+                downloads: 20_000,
+                ..package(2, "gitui")
+            },
+        ]);
+        app.apply_list_op(ListOp::Search("tui".into()));
+        app.apply_list_op(ListOp::MinDownloads(1_000));
+        assert_eq!(app.filtered_crates.len(), 2);
+        assert_eq!(chip_label(&app.list_ops[1]), "mindl:1.0K");
 
-        if handle_events(app)? {
-            break;
-        }
+        press(&mut app, KeyCode::Char('1'));
+        assert_eq!(app.list_ops, vec![ListOp::MinDownloads(1_000)]);
+        assert_eq!(app.filtered_crates.len(), 2);
+        assert!(app.filtered_crates.iter().all(|c| c.downloads >= 1_000));
+
+        press(&mut app, KeyCode::Char('3'));
+        assert_eq!(app.status_message, "No filter #3");
+        press(&mut app, KeyCode::Backspace);
+        assert_eq!(app.list_ops.len(), 2);
     }
-    Ok(())
-}
 
-fn format_number(n: u64) -> String {
-    if n >= 1_000_000 {
-        format!("{:.1}M", n as f64 / 1_000_000.0)
-    } else if n >= 1_000 {
-        format!("{:.1}K", n as f64 / 1_000.0)
-    } else {
-        n.to_string()
+    #[test]
+    fn test_issue_report_describes_versions_and_list_state() {
+        let mut app = test_app(&["ratatui", "gitui"]);
+        app.apply_list_op(ListOp::Search("git".into()));
+
+        let report = app.issue_report(Some((120, 40)));
+        assert!(report.starts_with("### Environment\n"));
+        assert!(report.contains(concat!("- ratcrate-tui: ", env!("CARGO_PKG_VERSION"))));
+        assert!(report.contains("- terminal: 120x40, "));
+        assert!(report.contains("- view: List, list: search! 'git' (1 of 2 crates)"));
+        assert!(report.contains("- selected: gitui"));
+        assert!(report.contains("[ui]\ncompact_list = false"));
     }
-}
 
-// ============================================================================
-// Note: cache.rs and types.rs are EXACTLY the same as ratcrate-cli
-// Just copy them from the CLI project!
-// ============================================================================
+    #[test]
+    fn test_count_search_agrees_with_search() {
+        let mut app = test_app(&["ratatui", "gitui"]);
+        app.command_input = "count search ratui".to_string();
+        app.execute_command();
+        assert_eq!(app.status_message, "1 crates match search 'ratui'");
+
+        app.command_input = "count search! ratui".to_string();
+        app.execute_command();
+        assert_eq!(app.status_message, "0 crates match search! 'ratui'");
+        assert_eq!(app.filtered_crates.len(), 2);
+    }
+
+    #[test]
+    fn test_match_count_toggles_to_percent() {
+        assert_eq!(match_count(3, 12, false), "3/12");
+        assert_eq!(match_count(3, 12, true), "25%");
+        assert_eq!(match_count(1, 300, true), "0.3%");
+        assert_eq!(match_count(0, 0, true), "0%");
+
+        let mut app = test_app(&["ratatui", "gitui"]);
+        press(&mut app, KeyCode::Char('%'));
+        assert!(app.count_as_percent);
+    }
+}
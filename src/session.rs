@@ -0,0 +1,48 @@
+//! session.rs — State remembered between runs
+//!
+//! The session file lives next to the data cache (per profile) and is rewritten on quit.
+//! Missing or unreadable session files are treated as an empty session.
+//
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::cache::get_profile_dir;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Session {
+    /// `id` of the crate selected when the app last quit
+    pub selected_id: Option<String>,
+    /// `metadata.version` of the dataset seen last time
+    pub data_version: Option<String>,
+    /// Number of crates in that dataset
+    pub crate_count: Option<usize>,
+    /// `id`s of recently viewed crates, most recent first
+    pub recently_viewed: Vec<String>,
+}
+
+/// Get the session file path
+pub fn get_session_file() -> Result<PathBuf> {
+    Ok(get_profile_dir()?.join("session.json"))
+}
+
+impl Session {
+    /// Load the previous session, falling back to an empty one.
+    pub fn load() -> Self {
+        get_session_file()
+            .ok()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// Write the session file.
+    pub fn save(&self) -> Result<()> {
+        let path = get_session_file()?;
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(&path, json)
+            .with_context(|| format!("Failed to write session file {}", path.display()))
+    }
+}
@@ -0,0 +1,313 @@
+//! sort.rs — Orderings for the crate list
+//!
+//! A sort is written as `<key>`, `<key>-<asc|desc>` or `<key> <asc|desc>`, e.g.
+//! `downloads-desc`, `name asc` or `name`.
+//! Without a direction, names sort A→Z, name lengths shortest first and everything else
+//! largest/newest first. `<sort> then <sort>` adds a tiebreaker, e.g. `recent then name`;
+//! without one, ties are broken by name so the order is the same on every run.
+//
+use semver::Version;
+use std::cmp::Ordering;
+
+use crate::types::CratePackage;
+
+/// Field the list can be ordered by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortKey {
+    Name,
+    Downloads,
+    Recent,
+    Created,
+    Updated,
+    /// Length of the crate name
+    NameLen,
+    /// Semver-aware `version`; unparseable versions always sort last
+    Version,
+}
+
+impl SortKey {
+    /// All keys, in the order they are listed to the user
+    pub const ALL: [SortKey; 7] = [
+        SortKey::Name,
+        SortKey::Downloads,
+        SortKey::Recent,
+        SortKey::Created,
+        SortKey::Updated,
+        SortKey::NameLen,
+        SortKey::Version,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            SortKey::Name => "name",
+            SortKey::Downloads => "downloads",
+            SortKey::Recent => "recent",
+            SortKey::Created => "created",
+            SortKey::Updated => "updated",
+            SortKey::NameLen => "namelen",
+            SortKey::Version => "version",
+        }
+    }
+
+    /// Direction used when a sort names no direction
+    fn descending_by_default(self) -> bool {
+        !matches!(self, SortKey::Name | SortKey::NameLen)
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|k| k.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Comparison on this key in the given direction. Unparseable versions sort last
+    /// either way.
+    fn compare(self, descending: bool, a: &CratePackage, b: &CratePackage) -> Ordering {
+        let directed = |ord: Ordering| if descending { ord.reverse() } else { ord };
+        if self == SortKey::Version {
+            let version = |c: &CratePackage| Version::parse(c.version.trim()).ok();
+            return match (version(a), version(b)) {
+                (Some(x), Some(y)) => directed(x.cmp(&y)),
+                (x, y) => x.is_none().cmp(&y.is_none()),
+            };
+        }
+        directed(self.ascending(a, b))
+    }
+
+    /// Ascending comparison on any key but `Version`.
+    fn ascending(self, a: &CratePackage, b: &CratePackage) -> Ordering {
+        match self {
+            SortKey::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+            SortKey::Downloads => a.downloads.cmp(&b.downloads),
+            SortKey::Recent => a.recent_downloads.cmp(&b.recent_downloads),
+            // RFC 3339 timestamps order correctly as strings
+            SortKey::Created => a.created_at.cmp(&b.created_at),
+            SortKey::Updated => a.updated_at.cmp(&b.updated_at),
+            SortKey::NameLen => a
+                .name
+                .chars()
+                .count()
+                .cmp(&b.name.chars().count())
+                .then_with(|| a.name.cmp(&b.name)),
+            SortKey::Version => Ordering::Equal,
+        }
+    }
+}
+
+/// A sort key plus direction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SortStep {
+    pub key: SortKey,
+    pub descending: bool,
+}
+
+impl SortStep {
+    /// Ascending by name, the tiebreaker when none is given
+    pub const BY_NAME: SortStep = SortStep {
+        key: SortKey::Name,
+        descending: false,
+    };
+
+    /// Parse `name`, `downloads-desc`, `created-asc`, ...
+    ///
+    /// # Errors
+    /// Returns a message listing the valid keys if the key or direction is unknown.
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let spec = spec.trim();
+        let (key, direction) = match spec.rsplit_once('-') {
+            Some((key, dir)) => (key, Some(dir)),
+            None => (spec, None),
+        };
+
+        let key = SortKey::from_name(key).ok_or_else(|| {
+            let keys: Vec<&str> = SortKey::ALL.iter().map(|k| k.name()).collect();
+            format!("Unknown sort key '{}' (use {})", key, keys.join(", "))
+        })?;
+        let descending = match direction.map(str::to_ascii_lowercase).as_deref() {
+            None => key.descending_by_default(),
+            Some("desc") => true,
+            Some("asc") => false,
+            Some(other) => {
+                return Err(format!(
+                    "Unknown sort direction '{}' (use asc or desc)",
+                    other
+                ));
+            }
+        };
+
+        Ok(Self { key, descending })
+    }
+
+    fn compare(&self, a: &CratePackage, b: &CratePackage) -> Ordering {
+        self.key.compare(self.descending, a, b)
+    }
+
+    /// Short description, e.g. "downloads desc"
+    pub fn label(&self) -> String {
+        format!(
+            "{} {}",
+            self.key.name(),
+            if self.descending { "desc" } else { "asc" }
+        )
+    }
+}
+
+/// A primary sort and the tiebreaker for crates it considers equal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SortOrder {
+    pub primary: SortStep,
+    pub then: SortStep,
+}
+
+impl SortOrder {
+    /// Sort by `primary`, breaking ties by name.
+    pub fn by(primary: SortStep) -> Self {
+        Self {
+            primary,
+            then: SortStep::BY_NAME,
+        }
+    }
+
+    /// Parse `<sort>` or `<sort> then <sort>`, e.g. `recent then name-desc` or
+    /// `name asc`.
+    ///
+    /// # Errors
+    /// Returns a message if either sort is invalid.
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        // Fold a separate direction word into its key: `name asc` is `name-asc`
+        let mut words: Vec<String> = vec![];
+        for word in spec.split_whitespace() {
+            let is_direction = ["asc", "desc"].iter().any(|d| word.eq_ignore_ascii_case(d));
+            match words.last_mut() {
+                Some(key) if is_direction && !key.eq_ignore_ascii_case("then") => {
+                    key.push('-');
+                    key.push_str(word);
+                }
+                _ => words.push(word.to_string()),
+            }
+        }
+        let words: Vec<&str> = words.iter().map(String::as_str).collect();
+        match words.as_slice() {
+            [primary] => Ok(Self::by(SortStep::parse(primary)?)),
+            [primary, then, tiebreak] if then.eq_ignore_ascii_case("then") => Ok(Self {
+                primary: SortStep::parse(primary)?,
+                then: SortStep::parse(tiebreak)?,
+            }),
+            _ => Err(format!(
+                "Invalid sort '{}' (use <key> [asc|desc] [then <key> [asc|desc]])",
+                spec.trim()
+            )),
+        }
+    }
+
+    /// Sort in place by the primary key, then the tiebreaker. The sort is stable, so crates
+    /// equal on both keep their current order.
+    pub fn sort(&self, crates: &mut [CratePackage]) {
+        crates.sort_by(|a, b| {
+            self.primary
+                .compare(a, b)
+                .then_with(|| self.then.compare(a, b))
+        });
+    }
+
+    /// Whether the tiebreaker can change anything (sorting by a key twice can't)
+    pub fn has_tiebreak(&self) -> bool {
+        self.then.key != self.primary.key
+    }
+
+    /// Short description, e.g. "downloads desc, then name asc"
+    pub fn label(&self) -> String {
+        if self.has_tiebreak() {
+            format!("{}, then {}", self.primary.label(), self.then.label())
+        } else {
+            self.primary.label()
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Unit tests for sort.rs
+// ---------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_sort_order() {
+        let order = SortStep::parse("downloads-desc").unwrap();
+        assert_eq!(order.key, SortKey::Downloads);
+        assert!(order.descending);
+
+        assert!(!SortStep::parse("Name").unwrap().descending);
+        assert!(!SortStep::parse("recent-asc").unwrap().descending);
+        assert!(SortStep::parse("stars").is_err());
+        assert!(SortStep::parse("name-sideways").is_err());
+        assert!(!SortStep::parse("namelen").unwrap().descending);
+
+        let chained = SortOrder::parse("recent then name-desc").unwrap();
+        assert_eq!(chained.primary.key, SortKey::Recent);
+        assert_eq!(chained.then.key, SortKey::Name);
+        assert!(chained.then.descending);
+        assert_eq!(SortOrder::parse("recent").unwrap().then, SortStep::BY_NAME);
+        assert!(SortOrder::parse("recent name").is_err());
+        assert!(SortOrder::parse("recent then").is_err());
+
+        let spaced = SortOrder::parse("name asc").unwrap();
+        assert_eq!(spaced, SortOrder::parse("name-asc").unwrap());
+        let spaced = SortOrder::parse("recent DESC then name desc").unwrap();
+        assert_eq!(
+            spaced,
+            SortOrder::parse("recent-desc then name-desc").unwrap()
+        );
+        assert!(SortOrder::parse("asc").is_err());
+        assert!(SortOrder::parse("name asc desc").is_err());
+    }
+
+    #[test]
+    fn test_ties_are_broken_by_the_second_key() {
+        let mut crates: Vec<CratePackage> = [("b", 0), ("c", 5), ("a", 0)]
+            .iter()
+            .map(|(name, recent)| CratePackage {
+                name: name.to_string(),
+                recent_downloads: *recent,
+                ..Default::default()
+            })
+            .collect();
+        let names = |crates: &[CratePackage]| -> Vec<String> {
+            crates.iter().map(|c| c.name.clone()).collect()
+        };
+
+        SortOrder::parse("recent").unwrap().sort(&mut crates);
+        assert_eq!(names(&crates), ["c", "a", "b"]);
+        SortOrder::parse("recent then name-desc")
+            .unwrap()
+            .sort(&mut crates);
+        assert_eq!(names(&crates), ["c", "b", "a"]);
+    }
+
+    #[test]
+    fn test_version_sort_is_semver_aware_with_junk_last() {
+        let mut crates: Vec<CratePackage> = ["0.10.0", "garbage", "0.9.3", "1.0.0-beta.1", "1.0.0"]
+            .iter()
+            .map(|v| CratePackage {
+                version: v.to_string(),
+                ..Default::default()
+            })
+            .collect();
+        let versions = |crates: &[CratePackage]| -> Vec<String> {
+            crates.iter().map(|c| c.version.clone()).collect()
+        };
+
+        SortOrder::parse("version-asc").unwrap().sort(&mut crates);
+        assert_eq!(
+            versions(&crates),
+            ["0.9.3", "0.10.0", "1.0.0-beta.1", "1.0.0", "garbage"]
+        );
+
+        SortOrder::parse("version").unwrap().sort(&mut crates);
+        assert_eq!(
+            versions(&crates),
+            ["1.0.0", "1.0.0-beta.1", "0.10.0", "0.9.3", "garbage"]
+        );
+    }
+}
@@ -0,0 +1,219 @@
+//! theme.rs — Resolved visual settings for the ratcrate TUI
+//!
+//! `ThemeConfig` holds what the user wrote; `Theme` holds validated ratatui values ready to use
+//! while rendering.
+//
+use anyhow::{Result, bail};
+use ratatui::{
+    style::{Color, Modifier, Style},
+    widgets::{Block, BorderType, Borders},
+};
+use std::env;
+use std::str::FromStr;
+
+use crate::config::ThemeConfig;
+
+/// Names accepted by `--theme`
+pub const BUILTIN_THEMES: [&str; 4] = ["default", "ocean", "forest", "mono"];
+
+#[derive(Debug, Clone)]
+pub struct Theme {
+    pub borders: Borders,
+    pub border_type: BorderType,
+    pub border_color: Color,
+    /// Plain body text; `Color::Reset` uses the terminal's own foreground
+    pub text_color: Color,
+    /// Background of the selected row
+    pub highlight_bg: Color,
+    /// Marker before the selected row; `None` uses the icon set's marker
+    pub highlight_symbol: Option<String>,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            borders: Borders::ALL,
+            border_type: BorderType::Plain,
+            border_color: Color::Cyan,
+            text_color: Color::Reset,
+            highlight_bg: Color::Rgb(60, 60, 80),
+            highlight_symbol: None,
+        }
+    }
+}
+
+impl Theme {
+    /// Build a theme from the `[theme]` config section.
+    ///
+    /// # Errors
+    /// Returns an error naming the offending value if a border style or color is unknown.
+    pub fn from_config(config: &ThemeConfig) -> Result<Self> {
+        let (borders, border_type) = match config.border.to_lowercase().as_str() {
+            "plain" => (Borders::ALL, BorderType::Plain),
+            "rounded" => (Borders::ALL, BorderType::Rounded),
+            "double" => (Borders::ALL, BorderType::Double),
+            "thick" => (Borders::ALL, BorderType::Thick),
+            "none" => (Borders::NONE, BorderType::Plain),
+            other => bail!(
+                "Unknown border style '{}' (expected plain, rounded, double, thick or none)",
+                other
+            ),
+        };
+
+        Ok(Self {
+            borders,
+            border_type,
+            border_color: parse_color(&config.border_color)?,
+            text_color: parse_color(&config.text_color)?,
+            highlight_bg: resolve_highlight(
+                &config.highlight_bg,
+                supports_truecolor(),
+                light_background(),
+            )?,
+            highlight_symbol: config.highlight_symbol.clone(),
+        })
+    }
+
+    /// Style for plain body text, readable on light and dark terminals alike by default.
+    pub fn text(&self) -> Style {
+        Style::default().fg(self.text_color)
+    }
+
+    /// Style of the selected row in lists.
+    pub fn highlight(&self) -> Style {
+        Style::default()
+            .bg(self.highlight_bg)
+            .add_modifier(Modifier::BOLD)
+    }
+
+    /// Marker before the selected row, falling back to the icon set's `default`.
+    pub fn highlight_symbol<'a>(&'a self, default: &'a str) -> &'a str {
+        self.highlight_symbol.as_deref().unwrap_or(default)
+    }
+
+    /// The base `Block` every pane is drawn in.
+    pub fn block(&self) -> Block<'static> {
+        Block::default()
+            .borders(self.borders)
+            .border_type(self.border_type)
+            .border_style(Style::default().fg(self.border_color))
+    }
+}
+
+/// Settings of a built-in theme, for `--theme`.
+///
+/// # Errors
+/// Returns an error listing the built-in names if `name` is not one of them.
+pub fn builtin(name: &str) -> Result<ThemeConfig> {
+    let (border, border_color) = match name.to_lowercase().as_str() {
+        "default" => return Ok(ThemeConfig::default()),
+        "ocean" => ("rounded", "blue"),
+        "forest" => ("rounded", "green"),
+        "mono" => ("plain", "gray"),
+        other => bail!(
+            "Unknown theme '{}' (expected one of: {})",
+            other,
+            BUILTIN_THEMES.join(", ")
+        ),
+    };
+    Ok(ThemeConfig {
+        border: border.to_string(),
+        border_color: border_color.to_string(),
+        ..ThemeConfig::default()
+    })
+}
+
+/// Pick the selection background. "auto" suits the terminal's light or dark background;
+/// without truecolor support, RGB colors become the nearest 256-color index.
+fn resolve_highlight(value: &str, truecolor: bool, light: bool) -> Result<Color> {
+    let color = if value.eq_ignore_ascii_case("auto") {
+        if light {
+            Color::Rgb(200, 200, 220)
+        } else {
+            Color::Rgb(60, 60, 80)
+        }
+    } else {
+        parse_color(value)?
+    };
+    Ok(match color {
+        Color::Rgb(r, g, b) if !truecolor => Color::Indexed(rgb_to_ansi256(r, g, b)),
+        color => color,
+    })
+}
+
+/// Whether the terminal advertises 24-bit color through `COLORTERM`.
+fn supports_truecolor() -> bool {
+    env::var("COLORTERM")
+        .map(|v| matches!(v.to_lowercase().as_str(), "truecolor" | "24bit"))
+        .unwrap_or(false)
+}
+
+/// Whether `COLORFGBG` (set by rxvt, Konsole and others) reports a light background.
+/// Unknown means dark, the more common default.
+fn light_background() -> bool {
+    env::var("COLORFGBG")
+        .ok()
+        .and_then(|v| v.rsplit(';').next()?.parse::<u8>().ok())
+        .is_some_and(|bg| bg == 7 || bg >= 9)
+}
+
+/// Nearest color in the xterm 6x6x6 cube or grayscale ramp.
+fn rgb_to_ansi256(r: u8, g: u8, b: u8) -> u8 {
+    if r == g && g == b {
+        return match r {
+            0..8 => 16,
+            248.. => 231,
+            _ => 232 + (r - 8) / 10,
+        };
+    }
+    let level = |c: u8| match c {
+        0..48 => 0,
+        48..115 => 1,
+        _ => (c - 35) / 40,
+    };
+    16 + 36 * level(r) + 6 * level(g) + level(b)
+}
+
+/// Parse a color name, index or hex string from the config file.
+fn parse_color(value: &str) -> Result<Color> {
+    Color::from_str(value).map_err(|_| anyhow::anyhow!("Unknown color '{}'", value))
+}
+
+// ---------------------------------------------------------------------------
+// Unit tests for theme.rs
+// ---------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_highlight_adapts_to_background_and_color_support() {
+        assert_eq!(
+            resolve_highlight("auto", true, false).unwrap(),
+            Color::Rgb(60, 60, 80)
+        );
+        assert_eq!(
+            resolve_highlight("auto", true, true).unwrap(),
+            Color::Rgb(200, 200, 220)
+        );
+        assert_eq!(
+            resolve_highlight("auto", false, false).unwrap(),
+            Color::Indexed(59)
+        );
+        assert_eq!(
+            resolve_highlight("#000000", false, false).unwrap(),
+            Color::Indexed(16)
+        );
+        // Greys past the top of the 24-step ramp map to cube white instead of overflowing
+        assert_eq!(
+            resolve_highlight("#f8f8f8", false, false).unwrap(),
+            Color::Indexed(231)
+        );
+        assert_eq!(
+            resolve_highlight("#ffffff", false, false).unwrap(),
+            Color::Indexed(231)
+        );
+        assert_eq!(resolve_highlight("blue", false, true).unwrap(), Color::Blue);
+        assert!(resolve_highlight("nope", true, false).is_err());
+    }
+}
@@ -0,0 +1,157 @@
+//! tryout.rs — Cargo commands run on the user's behalf
+//!
+//! `:try` scaffolds a scratch project that depends on a crate and `:add` runs `cargo add` in
+//! an existing project. Both touch the filesystem and the network, so each is first built as
+//! a `Plan` whose exact commands are shown for confirmation before anything runs.
+//
+use std::env;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use anyhow::{Context, Result, bail};
+
+/// Directory under the system temp dir that holds `:try` projects
+const TRY_DIR: &str = "ratcrate-try";
+
+/// One command and the directory it runs in
+#[derive(Debug, Clone, PartialEq)]
+pub struct Step {
+    pub dir: PathBuf,
+    pub program: &'static str,
+    pub args: Vec<String>,
+}
+
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.program)?;
+        for arg in &self.args {
+            write!(f, " {}", arg)?;
+        }
+        Ok(())
+    }
+}
+
+/// The commands an action will run, in order, and the project they leave behind
+#[derive(Debug, Clone, PartialEq)]
+pub struct Plan {
+    pub steps: Vec<Step>,
+    pub project: PathBuf,
+}
+
+impl Plan {
+    /// Scaffold `<temp>/ratcrate-try/try-<name>` and add the crate to it.
+    ///
+    /// An existing project from an earlier `:try` is reused rather than recreated.
+    pub fn try_crate(name: &str) -> Self {
+        let root = env::temp_dir().join(TRY_DIR);
+        let project_name = format!("try-{}", name);
+        let project = root.join(&project_name);
+
+        let mut steps = vec![];
+        if !project.exists() {
+            steps.push(Step {
+                dir: root,
+                program: "cargo",
+                args: vec!["new".into(), "--vcs".into(), "none".into(), project_name],
+            });
+        }
+        steps.push(add_step(name, &project));
+        Plan { steps, project }
+    }
+
+    /// Run `cargo add <name>` in the project at `dir`.
+    ///
+    /// # Errors
+    /// Fails when `dir` has no `Cargo.toml`, so nothing runs outside a Cargo project.
+    pub fn add_to(name: &str, dir: &Path) -> Result<Self> {
+        if !dir.join("Cargo.toml").is_file() {
+            bail!("no Cargo.toml in {}", dir.display());
+        }
+        Ok(Plan {
+            steps: vec![add_step(name, dir)],
+            project: dir.to_path_buf(),
+        })
+    }
+
+    /// The plan as one line for the confirmation prompt: where each command runs, then the
+    /// command itself.
+    pub fn describe(&self) -> String {
+        let mut out = vec![];
+        let mut dir: Option<&Path> = None;
+        for step in &self.steps {
+            if dir != Some(step.dir.as_path()) {
+                out.push(format!("cd {}", step.dir.display()));
+                dir = Some(&step.dir);
+            }
+            out.push(step.to_string());
+        }
+        out.join(" && ")
+    }
+
+    /// Run every step, stopping at the first failure.
+    ///
+    /// Output is captured so it can't scribble over the TUI; a failing command's last stderr
+    /// line becomes the error.
+    pub fn run(&self) -> Result<PathBuf> {
+        for step in &self.steps {
+            std::fs::create_dir_all(&step.dir)
+                .with_context(|| format!("Failed to create {}", step.dir.display()))?;
+            let output = Command::new(step.program)
+                .args(&step.args)
+                .current_dir(&step.dir)
+                .stdin(Stdio::null())
+                .output()
+                .with_context(|| format!("Failed to run `{}`", step))?;
+            if !output.status.success() {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                let reason = stderr
+                    .lines()
+                    .rev()
+                    .find(|l| !l.trim().is_empty())
+                    .unwrap_or("no output");
+                bail!("`{}` failed: {}", step, reason.trim());
+            }
+        }
+        Ok(self.project.clone())
+    }
+}
+
+fn add_step(name: &str, dir: &Path) -> Step {
+    Step {
+        dir: dir.to_path_buf(),
+        program: "cargo",
+        args: vec!["add".into(), name.into()],
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Unit tests for tryout.rs
+// ---------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plans_describe_their_commands_and_directories() {
+        let plan = Plan::try_crate("no-such-crate-for-tests");
+        let root = env::temp_dir().join(TRY_DIR);
+        let project = root.join("try-no-such-crate-for-tests");
+        assert_eq!(
+            plan.describe(),
+            format!(
+                "cd {} && cargo new --vcs none try-no-such-crate-for-tests && cd {} && cargo add no-such-crate-for-tests",
+                root.display(),
+                project.display()
+            )
+        );
+
+        let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let add = Plan::add_to("ratatui", manifest_dir).unwrap();
+        assert_eq!(
+            add.describe(),
+            format!("cd {} && cargo add ratatui", manifest_dir.display())
+        );
+        assert!(Plan::add_to("ratatui", &manifest_dir.join("src")).is_err());
+    }
+}
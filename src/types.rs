@@ -5,8 +5,15 @@
 //! with `serde_json`.
 //
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fmt;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Major version of `metadata.version` this build can read. Datasets with another major
+/// version may not match `CratePackage` and are rejected; minor versions go through
+/// `migrate`.
+pub const SUPPORTED_SCHEMA: u64 = 1;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 /// Describes a dependency entry for a crate (version and flags).
 pub struct RatatuiDependency {
     pub version: String,
@@ -14,7 +21,7 @@ pub struct RatatuiDependency {
     pub dev_dependency: bool,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 /// Primary crate metadata used to render list and detail views
 pub struct CratePackage {
     pub id: String,
@@ -33,13 +40,27 @@ pub struct CratePackage {
     pub is_core_library: bool,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Statistics {
     pub etag_cache_hits: usize,
     pub etag_cache_misses: usize,
     pub cache_hit_rate: f64,
 }
 
+impl Statistics {
+    /// Count a download attempt: `hit` when the server answered `304 Not Modified` to our
+    /// `If-None-Match`.
+    pub fn record_etag(&mut self, hit: bool) {
+        if hit {
+            self.etag_cache_hits += 1;
+        } else {
+            self.etag_cache_misses += 1;
+        }
+        let total = self.etag_cache_hits + self.etag_cache_misses;
+        self.cache_hit_rate = self.etag_cache_hits as f64 / total as f64;
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 /// Metadata about the dataset (generation time , totals, etc.)
 pub struct Metadata {
@@ -57,3 +78,210 @@ pub struct CratesData {
     pub metadata: Metadata,
     pub crates: Vec<CratePackage>,
 }
+
+#[cfg(test)]
+impl CratesData {
+    /// Wrap a list of crates with metadata derived from it (totals and core/community split),
+    /// so test fixtures don't have to spell out a metadata block.
+    pub fn from_crates(crates: Vec<CratePackage>) -> Self {
+        let core_libraries = crates.iter().filter(|c| c.is_core_library).count();
+        Self {
+            metadata: Metadata {
+                version: String::new(),
+                generated_at: chrono::Utc::now().to_rfc3339(),
+                total_crates: crates.len(),
+                core_libraries,
+                community_packages: crates.len() - core_libraries,
+                data_sources: vec![],
+                statistics: Statistics::default(),
+            },
+            crates,
+        }
+    }
+}
+
+/// A dataset whose `metadata.version` this build can't read
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaMismatch {
+    pub version: String,
+}
+
+impl fmt::Display for SchemaMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let age = match schema_version(&self.version) {
+            Some((major, _)) if major < SUPPORTED_SCHEMA => "too old",
+            Some(_) => "too new",
+            None => "not recognized",
+        };
+        write!(
+            f,
+            "dataset version '{}' is {} for this build (supports {}.x)",
+            self.version, age, SUPPORTED_SCHEMA
+        )
+    }
+}
+
+impl std::error::Error for SchemaMismatch {}
+
+/// Major and minor version from a `metadata.version` such as "1.2.0"
+fn schema_version(version: &str) -> Option<(u64, u64)> {
+    let mut parts = version.trim().split('.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next().map_or(Some(0), |m| m.parse().ok())?;
+    Some((major, minor))
+}
+
+impl CratesData {
+    /// Check the dataset's schema version after parsing and bring older minor versions up
+    /// to date.
+    ///
+    /// # Errors
+    /// Returns `SchemaMismatch` when the major version isn't `SUPPORTED_SCHEMA` or the
+    /// version can't be read.
+    pub fn upgrade(mut self) -> Result<Self, SchemaMismatch> {
+        match schema_version(&self.metadata.version) {
+            Some((SUPPORTED_SCHEMA, minor)) => {
+                migrate(&mut self, minor);
+                Ok(self)
+            }
+            _ => Err(SchemaMismatch {
+                version: self.metadata.version.clone(),
+            }),
+        }
+    }
+}
+
+/// Migration hook for minor schema changes: fill in or rename whatever a dataset of minor
+/// version `minor` is missing compared to the current shape. No minor version has needed
+/// one yet; add steps as `if minor < N { ... }`.
+fn migrate(_data: &mut CratesData, _minor: u64) {}
+
+/// Ids and names that occur more than once in a dataset, in first-seen order
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Duplicates {
+    pub ids: Vec<String>,
+    pub names: Vec<String>,
+}
+
+impl Duplicates {
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty() && self.names.is_empty()
+    }
+}
+
+/// Find crates sharing an `id` or a `name`. Each duplicated value is reported once.
+pub fn find_duplicates(crates: &[CratePackage]) -> Duplicates {
+    fn repeated<'a>(values: impl Iterator<Item = &'a str>) -> Vec<String> {
+        let mut seen = HashSet::new();
+        let mut reported = HashSet::new();
+        values
+            .filter(|v| !seen.insert(*v) && reported.insert(*v))
+            .map(str::to_string)
+            .collect()
+    }
+
+    Duplicates {
+        ids: repeated(crates.iter().map(|c| c.id.as_str())),
+        names: repeated(crates.iter().map(|c| c.name.as_str())),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Unit tests for types.rs
+// ---------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn package(id: &str, name: &str) -> CratePackage {
+        CratePackage {
+            id: id.to_string(),
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_find_duplicates_reports_each_repeat_once() {
+        let crates = vec![
+            package("1", "ratatui"),
+            package("2", "tui-input"),
+            package("1", "ratatui"),
+            package("3", "ratatui"),
+        ];
+
+        let dups = find_duplicates(&crates);
+        assert_eq!(dups.ids, vec!["1".to_string()]);
+        assert_eq!(dups.names, vec!["ratatui".to_string()]);
+        assert!(find_duplicates(&crates[..2]).is_empty());
+    }
+
+    #[test]
+    fn test_default_package_is_empty_community_crate() {
+        let c = CratePackage::default();
+        assert!(c.name.is_empty());
+        assert_eq!(c.downloads, 0);
+        assert_eq!(c.recent_downloads, 0);
+        assert!(c.categories.is_none());
+        assert!(!c.is_core_library);
+        assert!(!c.ratatui_dependency.optional);
+        assert!(!c.ratatui_dependency.dev_dependency);
+    }
+
+    #[test]
+    fn test_upgrade_rejects_other_schema_versions() {
+        let with_version = |version: &str| {
+            let mut data = CratesData::from_crates(vec![package("1", "ratatui")]);
+            data.metadata.version = version.to_string();
+            data
+        };
+
+        assert!(with_version("1.2.0").upgrade().is_ok());
+        assert!(with_version("1").upgrade().is_ok());
+
+        let too_new = with_version("2.0.0").upgrade().unwrap_err();
+        assert_eq!(
+            too_new.to_string(),
+            "dataset version '2.0.0' is too new for this build (supports 1.x)"
+        );
+        assert!(
+            with_version("0.9.1")
+                .upgrade()
+                .unwrap_err()
+                .to_string()
+                .contains("too old")
+        );
+        assert!(
+            with_version("latest")
+                .upgrade()
+                .unwrap_err()
+                .to_string()
+                .contains("not recognized")
+        );
+    }
+
+    #[test]
+    fn test_record_etag_updates_the_hit_rate() {
+        let mut stats = Statistics::default();
+        stats.record_etag(false);
+        assert_eq!(stats.cache_hit_rate, 0.0);
+        stats.record_etag(true);
+        stats.record_etag(true);
+        stats.record_etag(true);
+        assert_eq!((stats.etag_cache_hits, stats.etag_cache_misses), (3, 1));
+        assert_eq!(stats.cache_hit_rate, 0.75);
+    }
+
+    #[test]
+    fn test_from_crates_derives_totals() {
+        let mut core = package("1", "ratatui");
+        core.is_core_library = true;
+        let data =
+            CratesData::from_crates(vec![core, package("2", "tui-input"), package("3", "gitui")]);
+
+        assert_eq!(data.metadata.total_crates, 3);
+        assert_eq!(data.metadata.core_libraries, 1);
+        assert_eq!(data.metadata.community_packages, 2);
+        assert_eq!(data.crates.len(), 3);
+    }
+}